@@ -41,6 +41,9 @@ pub struct RawPlot {
     name: String,
     points: Vec<[f64; 2]>,
     expected_range: ExpectedPlotRange,
+    // Logs made before units were stored don't have it
+    #[serde(default)]
+    unit: Option<String>,
 }
 
 impl RawPlot {
@@ -49,8 +52,16 @@ impl RawPlot {
             name,
             points,
             expected_range,
+            unit: None,
         }
     }
+
+    /// The unit of the values, e.g. `"V"`. It is shown next to the name so it should not also be part of the name.
+    pub fn with_unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -60,6 +71,9 @@ impl RawPlot {
     pub fn expected_range(&self) -> ExpectedPlotRange {
         self.expected_range
     }
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
 }
 
 /// [`PlotLabel`] represents some text label that should be displayed in the plot
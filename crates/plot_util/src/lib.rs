@@ -1,5 +1,7 @@
 pub mod mipmap;
 
+use std::borrow::Cow;
+
 use egui::Color32;
 use egui_plot::{Line, PlotBounds, PlotPoint};
 
@@ -18,18 +20,173 @@ pub enum MipMapConfiguration {
     Disabled,
 }
 
+/// Values below or at this threshold are clamped to it before taking the logarithm when [`NonPositiveValues::Clamp`] is used.
+pub const LOG10_CLAMP_EPSILON: f64 = 1e-9;
+
+/// How to handle y-values that are zero or negative when plotting on a logarithmic scale
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum NonPositiveValues {
+    /// Leave the points out of the plotted line
+    #[default]
+    Skip,
+    /// Clamp the values to [`LOG10_CLAMP_EPSILON`]
+    Clamp,
+}
+
+/// A transform applied to the y-values of a series when it is painted.
+///
+/// The transform is applied in the following order:
+/// 1. An affine unit conversion (e.g. from °C to °F)
+/// 2. An optional base 10 logarithm
+/// 3. An affine transform (e.g. placing the series on a secondary axis)
+///
+/// The underlying [`PlotValues`] are never mutated, so the transform can change from frame to frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct YTransform {
+    unit_scale: f64,
+    unit_offset: f64,
+    log10: Option<NonPositiveValues>,
+    scale: f64,
+    offset: f64,
+}
+
+impl YTransform {
+    /// A transform that leaves the y-values as they are
+    pub const IDENTITY: Self = Self {
+        unit_scale: 1.0,
+        unit_offset: 0.0,
+        log10: None,
+        scale: 1.0,
+        offset: 0.0,
+    };
+
+    pub fn new(scale: f64, offset: f64) -> Self {
+        debug_assert!(
+            scale != 0.0,
+            "A y-transform with a scale of 0 is not invertible"
+        );
+        Self {
+            scale,
+            offset,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// Converts the y-values to another unit before the rest of the transform is applied.
+    pub fn with_unit_conversion(mut self, scale: f64, offset: f64) -> Self {
+        self.unit_scale = scale;
+        self.unit_offset = offset;
+        self
+    }
+
+    /// Returns a transform that maps y-values to their base 10 logarithm.
+    pub fn log10(non_positive: NonPositiveValues) -> Self {
+        Self::new(1.0, 0.0).with_log10(non_positive)
+    }
+
+    /// Takes the base 10 logarithm of the y-values before applying the affine part of the transform.
+    pub fn with_log10(mut self, non_positive: NonPositiveValues) -> Self {
+        self.log10 = Some(non_positive);
+        self
+    }
+
+    pub fn is_log10(&self) -> bool {
+        self.log10.is_some()
+    }
+
+    /// Returns the transform that maps the range `from` onto the range `to`.
+    ///
+    /// If `from` spans no distance, its value is mapped to the middle of `to`.
+    pub fn from_ranges(from: (f64, f64), to: (f64, f64)) -> Self {
+        let (from_min, from_max) = from;
+        let (to_min, to_max) = to;
+        let from_span = from_max - from_min;
+        let to_span = to_max - to_min;
+        if from_span == 0.0 || to_span == 0.0 {
+            return Self::new(1.0, (to_min + to_max) / 2.0 - from_min);
+        }
+        let scale = to_span / from_span;
+        Self::new(scale, to_min - from_min * scale)
+    }
+
+    /// Transforms a y-value. Returns [`f64::NAN`] for non-positive values if the transform is logarithmic and skips them.
+    #[inline]
+    pub fn apply(&self, y: f64) -> f64 {
+        let y = self.convert_unit(y);
+        let y = match self.log10 {
+            Some(non_positive) => log10_with(y, non_positive),
+            None => y,
+        };
+        y * self.scale + self.offset
+    }
+
+    /// Converts a y-value to the unit it is converted to (if any) without applying the rest of the transform.
+    #[inline]
+    pub fn convert_unit(&self, y: f64) -> f64 {
+        y * self.unit_scale + self.unit_offset
+    }
+
+    /// Maps a transformed y-value back to the value in the converted unit, i.e. only the unit conversion is not undone.
+    #[inline]
+    pub fn inverse(&self, y: f64) -> f64 {
+        let y = (y - self.offset) / self.scale;
+        if self.log10.is_some() {
+            10_f64.powf(y)
+        } else {
+            y
+        }
+    }
+
+    fn apply_to_points(&self, points: &mut Vec<[f64; 2]>) {
+        for p in points.iter_mut() {
+            p[1] = self.apply(p[1]);
+        }
+        if self.log10 == Some(NonPositiveValues::Skip) {
+            points.retain(|p| !p[1].is_nan());
+        }
+    }
+}
+
+/// Takes the base 10 logarithm of `y` with the specified handling of non-positive values.
+#[inline]
+pub fn log10_with(y: f64, non_positive: NonPositiveValues) -> f64 {
+    if y > 0.0 {
+        y.log10()
+    } else {
+        match non_positive {
+            NonPositiveValues::Skip => f64::NAN,
+            NonPositiveValues::Clamp => LOG10_CLAMP_EPSILON.log10(),
+        }
+    }
+}
+
+/// Paints the lines of all `plots`.
+///
+/// `y_transform` is queried for each plot and if it returns a [`YTransform`] it is applied to the y-values before painting.
+/// `label` returns the label to show in the legend for each plot.
 pub fn plot_lines<'pv>(
     plot_ui: &mut egui_plot::PlotUi,
     plots: impl Iterator<Item = &'pv PlotValues>,
     line_width: f32,
     mipmap_cfg: MipMapConfiguration,
     plots_width_pixels: usize,
+    y_transform: impl Fn(&PlotValues) -> Option<YTransform>,
+    label: impl Fn(&'pv PlotValues) -> Cow<'pv, str>,
 ) {
     let (x_lower, x_higher) = extended_x_plot_bound(plot_ui.plot_bounds(), 0.1);
     for plot_vals in plots {
+        let y_transform = y_transform(plot_vals);
+        let label = label(plot_vals);
         match mipmap_cfg {
             MipMapConfiguration::Disabled => {
-                plot_raw(plot_ui, plot_vals, line_width, (x_lower, x_higher));
+                plot_raw(
+                    plot_ui,
+                    plot_vals,
+                    &label,
+                    line_width,
+                    (x_lower, x_higher),
+                    y_transform,
+                );
             }
             MipMapConfiguration::Auto => {
                 let (level, idx_range) =
@@ -38,46 +195,70 @@ pub fn plot_lines<'pv>(
                 plot_with_mipmapping(
                     plot_ui,
                     plot_vals,
+                    &label,
                     line_width,
                     level,
                     (x_lower, x_higher),
                     idx_range,
+                    y_transform,
                 );
             }
             MipMapConfiguration::Manual(level) => {
                 plot_with_mipmapping(
                     plot_ui,
                     plot_vals,
+                    &label,
                     line_width,
                     level,
                     (x_lower, x_higher),
                     None,
+                    y_transform,
                 );
             }
         }
     }
 }
 
+#[allow(
+    clippy::too_many_arguments,
+    reason = "They are needed. Maybe a refactor could group some of them."
+)]
 fn plot_with_mipmapping(
     plot_ui: &mut egui_plot::PlotUi,
     plot_vals: &PlotValues,
+    label: &str,
     line_width: f32,
     mipmap_lvl: usize,
     x_range: (f64, f64),
     // if the range is already known then we can skip filtering
     known_idx_range: Option<(usize, usize)>,
+    y_transform: Option<YTransform>,
 ) {
     let (x_lower, x_higher) = x_range;
     // If the mipmap level is 0 or 1 plotting all data points is just as efficient.
     if mipmap_lvl < 2 {
-        plot_raw(plot_ui, plot_vals, line_width, (x_lower, x_higher));
+        plot_raw(
+            plot_ui,
+            plot_vals,
+            label,
+            line_width,
+            (x_lower, x_higher),
+            y_transform,
+        );
     } else {
         let (plot_points_min, plot_points_max) = plot_vals.get_level_or_max(mipmap_lvl);
         if plot_points_min.is_empty() {
             // In this case there was so few samples that downsampling just once was below the minimum threshold, so we just plot all samples
-            plot_raw(plot_ui, plot_vals, line_width, (x_lower, x_higher));
+            plot_raw(
+                plot_ui,
+                plot_vals,
+                label,
+                line_width,
+                (x_lower, x_higher),
+                y_transform,
+            );
         } else {
-            let (plot_points_min, plot_points_max) = match known_idx_range {
+            let (mut plot_points_min, mut plot_points_max) = match known_idx_range {
                 Some((start, end)) => {
                     extract_range_points(plot_points_min, plot_points_max, start, end)
                 }
@@ -86,10 +267,14 @@ fn plot_with_mipmapping(
                     filter_plot_points(plot_points_max, (x_lower, x_higher)),
                 ),
             };
+            if let Some(t) = y_transform {
+                t.apply_to_points(&mut plot_points_min);
+                t.apply_to_points(&mut plot_points_max);
+            }
 
             plot_min_max_lines(
                 plot_ui,
-                plot_vals.label(),
+                label,
                 (plot_points_min, plot_points_max),
                 line_width,
                 plot_vals.get_color(),
@@ -178,14 +363,19 @@ pub fn plot_labels(plot_ui: &mut egui_plot::PlotUi, plot_data: &PlotData, id_fil
 fn plot_raw(
     plot_ui: &mut egui_plot::PlotUi,
     plot_vals: &PlotValues,
+    label: &str,
     line_width: f32,
     x_min_max_ext: (f64, f64),
+    y_transform: Option<YTransform>,
 ) {
     let plot_points = plot_vals.get_raw();
-    let filtered_points = filter_plot_points(plot_points, x_min_max_ext);
+    let mut filtered_points = filter_plot_points(plot_points, x_min_max_ext);
+    if let Some(t) = y_transform {
+        t.apply_to_points(&mut filtered_points);
+    }
     let line = Line::new(filtered_points)
         .width(line_width)
-        .name(plot_vals.label())
+        .name(label)
         .color(plot_vals.get_color())
         .highlight(plot_vals.get_highlight());
     plot_ui.line(line);
@@ -254,6 +444,67 @@ pub fn filter_plot_points(points: &[[f64; 2]], x_range: (f64, f64)) -> Vec<[f64;
     filtered
 }
 
+/// The point found by [`find_closest_point`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPoint {
+    /// The point as it is painted, i.e. with the y-transform applied
+    pub painted: [f64; 2],
+    /// The y-value in the unit it is converted to (if any), i.e. without the log10 or secondary axis part of the y-transform
+    pub value_y: f64,
+    /// The squared screen distance to the target
+    pub dist_sq: f64,
+}
+
+/// Finds the point in `points` that is closest to `target` on the screen.
+///
+/// `points` has to be sorted by x, `dpos_dvalue` is the screen points per plot unit along x and y,
+/// and `y_transform` is applied to the y-values the same way as when they are painted.
+pub fn find_closest_point(
+    points: &[[f64; 2]],
+    target: [f64; 2],
+    dpos_dvalue: [f64; 2],
+    y_transform: Option<YTransform>,
+) -> Option<ClosestPoint> {
+    let [dpos_dx, dpos_dy] = dpos_dvalue;
+    let mut closest: Option<ClosestPoint> = None;
+    // Returns false once the x-distance alone is further away than the closest point found so far,
+    // as all remaining points in that direction are then further away as well.
+    let mut check_point = |point: &[f64; 2]| {
+        let dx = (point[0] - target[0]) * dpos_dx;
+        let dx_sq = dx * dx;
+        if closest.is_some_and(|c| dx_sq >= c.dist_sq) {
+            return false;
+        }
+        let y = y_transform.map_or(point[1], |t| t.apply(point[1]));
+        if y.is_nan() {
+            return true;
+        }
+        let dy = (y - target[1]) * dpos_dy;
+        let dist_sq = dx_sq + dy * dy;
+        if closest.is_none_or(|c| dist_sq < c.dist_sq) {
+            closest = Some(ClosestPoint {
+                painted: [point[0], y],
+                value_y: y_transform.map_or(point[1], |t| t.convert_unit(point[1])),
+                dist_sq,
+            });
+        }
+        true
+    };
+
+    let split_idx = points.partition_point(|p| p[0] < target[0]);
+    for p in &points[split_idx..] {
+        if !check_point(p) {
+            break;
+        }
+    }
+    for p in points[..split_idx].iter().rev() {
+        if !check_point(p) {
+            break;
+        }
+    }
+    closest
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +554,82 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_y_transform_from_ranges() {
+        let t = YTransform::from_ranges((0.0, 5000.0), (0.0, 1.0));
+        assert_eq!(t.apply(0.0), 0.0);
+        assert_eq!(t.apply(5000.0), 1.0);
+        assert_eq!(t.apply(2500.0), 0.5);
+        assert_eq!(t.inverse(0.5), 2500.0);
+    }
+
+    #[test]
+    fn test_y_transform_from_flat_range() {
+        let t = YTransform::from_ranges((3.0, 3.0), (0.0, 10.0));
+        assert_eq!(t.apply(3.0), 5.0);
+        assert_eq!(t.inverse(5.0), 3.0);
+    }
+
+    #[test]
+    fn test_y_transform_log10_skip_non_positive() {
+        let t = YTransform::log10(NonPositiveValues::Skip);
+        let mut points = vec![[0.0, 100.0], [1.0, 0.0], [2.0, -5.0], [3.0, 0.001]];
+        t.apply_to_points(&mut points);
+        assert_eq!(points, vec![[0.0, 2.0], [3.0, -3.0]]);
+        assert_eq!(t.inverse(2.0), 100.0);
+    }
+
+    #[test]
+    fn test_y_transform_log10_clamp_non_positive() {
+        let t = YTransform::log10(NonPositiveValues::Clamp);
+        let mut points = vec![[0.0, 10.0], [1.0, 0.0], [2.0, -5.0]];
+        t.apply_to_points(&mut points);
+        let clamped = LOG10_CLAMP_EPSILON.log10();
+        assert_eq!(points, vec![[0.0, 1.0], [1.0, clamped], [2.0, clamped]]);
+    }
+
+    #[test]
+    fn test_y_transform_unit_conversion_not_inverted() {
+        // °C to °F placed on a secondary axis scaled by 2
+        let t = YTransform::new(2.0, 0.0).with_unit_conversion(1.8, 32.0);
+        assert_eq!(t.apply(100.0), 424.0);
+        assert_eq!(t.inverse(424.0), 212.0);
+    }
+
+    #[test]
+    fn test_find_closest_point() {
+        let points: Vec<[f64; 2]> = (0..100).map(|i| [i as f64, (i % 10) as f64]).collect();
+        let closest = find_closest_point(&points, [42.4, 2.1], [1.0, 1.0], None).unwrap();
+        assert_eq!(closest.painted, [42.0, 2.0]);
+        assert_eq!(closest.value_y, 2.0);
+        assert!(closest.dist_sq < 0.2);
+    }
+
+    #[test]
+    fn test_find_closest_point_value_is_not_log_or_secondary_scaled() {
+        let points = [[0.0, 10.0], [1.0, 1000.0]];
+        // °C to °F on a log10 axis that is scaled by 2
+        let t = YTransform::new(2.0, 0.0)
+            .with_log10(NonPositiveValues::Skip)
+            .with_unit_conversion(1.8, 32.0);
+        let closest = find_closest_point(&points, [0.0, 3.4], [1.0, 1.0], Some(t)).unwrap();
+        assert_eq!(closest.painted, [0.0, t.apply(10.0)]);
+        assert_eq!(closest.value_y, 50.0);
+    }
+
+    #[test]
+    fn test_find_closest_point_scaled_screen_distance() {
+        let points = [[0.0, 0.0], [10.0, 1.0]];
+        // A large y-distance is small on the screen if the y-axis spans a large range
+        let closest = find_closest_point(&points, [1.0, 1.0], [1.0, 0.001], None).unwrap();
+        assert_eq!(closest.painted, [0.0, 0.0]);
+        let closest = find_closest_point(&points, [1.0, 1.0], [0.01, 1.0], None).unwrap();
+        assert_eq!(closest.painted, [10.0, 1.0]);
+    }
+
+    #[test]
+    fn test_find_closest_point_empty() {
+        assert_eq!(find_closest_point(&[], [0.0, 0.0], [1.0, 1.0], None), None);
+    }
 }
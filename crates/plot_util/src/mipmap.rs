@@ -137,6 +137,11 @@ impl<T: Num + ToPrimitive + FromPrimitive + Copy + PartialOrd + Default> MipMap2
         self.data.len()
     }
 
+    /// Returns the total number of points on all levels
+    pub fn total_points(&self) -> usize {
+        self.data.iter().map(Vec::len).sum()
+    }
+
     /// Returns the data on given level.
     /// Level `0` returns the source data; the higher the level, the higher the compression (i.e. smaller vectors are returned).
     /// If the level is out of bounds, returns None
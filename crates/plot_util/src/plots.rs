@@ -28,6 +28,15 @@ impl Plots {
         total_points
     }
 
+    /// Returns the approximate number of bytes used by the points of all plots, see [`PlotValues::estimated_memory_bytes`]
+    pub fn estimated_memory_bytes(&self) -> usize {
+        [&self.percentage, &self.one_to_hundred, &self.thousands]
+            .iter()
+            .flat_map(|plot_data| plot_data.plots())
+            .map(PlotValues::estimated_memory_bytes)
+            .sum()
+    }
+
     pub fn percentage(&self) -> &PlotData {
         &self.percentage
     }
@@ -55,11 +55,57 @@ impl PlotData {
                 raw_plot.name().to_owned(),
                 log_id,
             )
-            .color(self.auto_color());
+            .color(self.auto_color())
+            .unit(raw_plot.unit().map(ToOwned::to_owned));
             self.plots.push(new_plot);
         }
     }
 
+    /// Concatenates the plots and labels of the logs with the `other_log_ids` onto the plots with the same name of the log with `log_id`.
+    ///
+    /// Points are kept sorted by time. If the logs overlap, points at a timestamp that already has a point are dropped.
+    ///
+    /// # Returns
+    ///
+    /// The number of dropped points.
+    pub fn concatenate_logs(&mut self, log_id: u16, other_log_ids: &[u16]) -> usize {
+        let (others, mut plots): (Vec<PlotValues>, Vec<PlotValues>) = self
+            .plots
+            .drain(..)
+            .partition(|pv| other_log_ids.contains(&pv.log_id));
+        let mut dropped_points = 0;
+        for other in others {
+            if let Some(plot) = plots
+                .iter_mut()
+                .find(|pv| pv.log_id == log_id && pv.name == other.name)
+            {
+                dropped_points += plot.append_points(other.raw_plot);
+            } else {
+                // The log doesn't have a plot with this name so it simply becomes part of the log
+                plots.push(
+                    PlotValues::new(other.raw_plot, other.name, log_id)
+                        .color(other.color)
+                        .unit(other.unit),
+                );
+            }
+        }
+        self.plots = plots;
+        for plot_labels in &mut self.plot_labels {
+            if other_log_ids.contains(&plot_labels.log_id) {
+                plot_labels.log_id = log_id;
+            }
+        }
+        dropped_points
+    }
+
+    /// Removes the plots and labels of the log with `log_id`, returning the removed plots.
+    pub fn remove_log(&mut self, log_id: u16) -> Vec<PlotValues> {
+        let (removed, plots) = self.plots.drain(..).partition(|pv| pv.log_id == log_id);
+        self.plots = plots;
+        self.plot_labels.retain(|pl| pl.log_id != log_id);
+        removed
+    }
+
     fn auto_color(&mut self) -> Color32 {
         // source: https://docs.rs/egui_plot/0.29.0/src/egui_plot/plot_ui.rs.html#21
         // should be replaced/updated if they improve their implementation or provide a public API for this
@@ -82,6 +128,8 @@ pub struct PlotValues {
     label: String,
     color: Color32,
     highlight: bool,
+    #[serde(default)]
+    unit: Option<String>,
 }
 
 type PointList<'pl> = &'pl [[f64; 2]];
@@ -110,6 +158,7 @@ impl PlotValues {
             // Color32::TRANSPARENT means we auto assign one
             color: Color32::TRANSPARENT,
             highlight: false,
+            unit: None,
         }
     }
 
@@ -126,6 +175,24 @@ impl PlotValues {
         self.color
     }
 
+    #[inline]
+    pub fn get_color_mut(&mut self) -> &mut Color32 {
+        &mut self.color
+    }
+
+    /// Unit of the values, e.g. `"V"`. Default is `None` for plots where the unit is unknown or part of the name.
+    #[inline]
+    pub fn unit(mut self, unit: Option<String>) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Unit of the values.
+    #[inline]
+    pub fn get_unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
     pub fn get_raw(&self) -> PointList {
         &self.raw_plot
     }
@@ -150,6 +217,27 @@ impl PlotValues {
         )
     }
 
+    /// Returns the approximate number of bytes used by the points of the plot, including the downsampled points
+    pub fn estimated_memory_bytes(&self) -> usize {
+        let points =
+            self.raw_plot.len() + self.mipmap_min.total_points() + self.mipmap_max.total_points();
+        points * size_of::<[f64; 2]>()
+    }
+
+    /// Returns the minimum and maximum y-value of the plot or [`None`] if there are no points.
+    ///
+    /// Uses the most downsampled mipmap level when available, so it is cheap enough to call every frame.
+    pub fn y_min_max(&self) -> Option<(f64, f64)> {
+        let (mut points_min, mut points_max) = self.get_max_level();
+        if points_min.is_empty() {
+            points_min = self.get_raw();
+            points_max = self.get_raw();
+        }
+        let y_min = points_min.iter().map(|p| p[1]).reduce(f64::min)?;
+        let y_max = points_max.iter().map(|p| p[1]).reduce(f64::max)?;
+        Some((y_min, y_max))
+    }
+
     pub fn mipmap_levels(&self) -> usize {
         self.mipmap_min.num_levels()
     }
@@ -168,6 +256,27 @@ impl PlotValues {
         self.recalc_mipmaps();
     }
 
+    /// Appends `points` and sorts all points by time, the points of `points` at a timestamp that the plot already has a point at are dropped.
+    ///
+    /// Points with the same timestamp within the plot or within `points` are all kept, they are not an overlap.
+    ///
+    /// Returns the number of dropped points.
+    fn append_points(&mut self, points: Vec<[f64; 2]>) -> usize {
+        let mut existing_timestamps: Vec<f64> = self.raw_plot.iter().map(|p| p[0]).collect();
+        existing_timestamps.sort_by(f64::total_cmp);
+        let point_count = points.len();
+        self.raw_plot.extend(points.into_iter().filter(|p| {
+            existing_timestamps
+                .binary_search_by(|t| t.total_cmp(&p[0]))
+                .is_err()
+        }));
+        let dropped_points = point_count - (self.raw_plot.len() - existing_timestamps.len());
+        // Stable sort so points with the same timestamp keep their order
+        self.raw_plot.sort_by(|a, b| a[0].total_cmp(&b[0]));
+        self.recalc_mipmaps();
+        dropped_points
+    }
+
     fn recalc_mipmaps(&mut self) {
         self.mipmap_min = MipMap2D::without_base(
             &self.raw_plot,
@@ -286,3 +395,101 @@ impl From<([f64; 2], String)> for PlotLabel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_y_min_max_few_points() {
+        let pv = PlotValues::new(vec![[0.0, 2.0], [1.0, -1.0], [2.0, 5.0]], "a".into(), 1);
+        assert_eq!(pv.y_min_max(), Some((-1.0, 5.0)));
+    }
+
+    #[test]
+    fn test_y_min_max_mipmapped() {
+        let points: Vec<[f64; 2]> = (0..5000).map(|i| [i as f64, (i % 100) as f64]).collect();
+        let pv = PlotValues::new(points, "a".into(), 1);
+        assert!(pv.mipmap_levels() > 1);
+        assert_eq!(pv.y_min_max(), Some((0.0, 99.0)));
+    }
+
+    #[test]
+    fn test_concatenate_logs() {
+        let mut plot_data = PlotData::default();
+        plot_data.plots_as_mut().extend([
+            PlotValues::new(vec![[0.0, 1.0], [1.0, 2.0]], "RPM".into(), 1),
+            PlotValues::new(vec![[1.0, 5.0], [2.0, 3.0]], "RPM".into(), 2),
+            PlotValues::new(vec![[2.0, 4.0]], "Current".into(), 2),
+        ]);
+        plot_data.add_plot_labels(StoredPlotLabels::new(vec![([2.0, 1.0], "a".into())], 2));
+
+        let dropped_points = plot_data.concatenate_logs(1, &[2]);
+        assert_eq!(dropped_points, 1);
+        assert_eq!(plot_data.plots().len(), 2);
+        let rpm = &plot_data.plots()[0];
+        assert_eq!(rpm.raw_plot(), [[0.0, 1.0], [1.0, 2.0], [2.0, 3.0]]);
+        let current = &plot_data.plots()[1];
+        assert_eq!(current.label(), "Current #1");
+        assert_eq!(plot_data.plot_labels()[0].log_id(), 1);
+    }
+
+    #[test]
+    fn test_concatenate_logs_keeps_duplicates_within_a_log() {
+        let mut plot_data = PlotData::default();
+        plot_data.plots_as_mut().extend([
+            PlotValues::new(vec![[0.0, 1.0], [0.0, 1.5], [1.0, 2.0]], "RPM".into(), 1),
+            PlotValues::new(vec![[2.0, 3.0], [2.0, 3.5]], "RPM".into(), 2),
+        ]);
+
+        assert_eq!(plot_data.concatenate_logs(1, &[2]), 0);
+        assert_eq!(
+            plot_data.plots()[0].raw_plot(),
+            [[0.0, 1.0], [0.0, 1.5], [1.0, 2.0], [2.0, 3.0], [2.0, 3.5]]
+        );
+    }
+
+    #[test]
+    fn test_remove_log() {
+        let mut plot_data = PlotData::default();
+        plot_data.plots_as_mut().extend([
+            PlotValues::new(vec![[0.0, 1.0]], "RPM".into(), 1),
+            PlotValues::new(vec![[1.0, 5.0]], "RPM".into(), 2),
+        ]);
+        plot_data.add_plot_labels(StoredPlotLabels::new(vec![([2.0, 1.0], "a".into())], 2));
+
+        let removed = plot_data.remove_log(2);
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].label(), "RPM #2");
+        assert_eq!(plot_data.plots().len(), 1);
+        assert_eq!(plot_data.plots()[0].log_id(), 1);
+        assert!(plot_data.plot_labels().is_empty());
+    }
+
+    #[test]
+    fn test_add_plot_keeps_unit() {
+        let mut plot_data = PlotData::default();
+        let raw_plot = RawPlot::new(
+            "Vbat".into(),
+            vec![[0.0, 24.0]],
+            log_if::prelude::ExpectedPlotRange::OneToOneHundred,
+        )
+        .with_unit("V");
+        plot_data.add_plot_if_not_exists(&raw_plot, 1);
+        assert_eq!(plot_data.plots()[0].name(), "Vbat");
+        assert_eq!(plot_data.plots()[0].get_unit(), Some("V"));
+    }
+
+    #[test]
+    fn test_estimated_memory_bytes_includes_mipmaps() {
+        let points: Vec<[f64; 2]> = (0..5000).map(|i| [i as f64, 1.0]).collect();
+        let pv = PlotValues::new(points, "a".into(), 1);
+        assert!(pv.estimated_memory_bytes() > 5000 * size_of::<[f64; 2]>());
+    }
+
+    #[test]
+    fn test_y_min_max_empty() {
+        let pv = PlotValues::new(vec![], "a".into(), 1);
+        assert_eq!(pv.y_min_max(), None);
+    }
+}
@@ -176,15 +176,17 @@ fn build_all_plots(entries: &[GeneratorLogEntry]) -> Vec<RawPlot> {
             ExpectedPlotRange::OneToOneHundred,
         ),
         RawPlot::new(
-            "Temp1 °C".into(),
+            "Temp1".into(),
             plot_points_from_log_entry(entries, |e| e.timestamp_ns(), |e| e.temp1.into()),
             ExpectedPlotRange::OneToOneHundred,
-        ),
+        )
+        .with_unit("°C"),
         RawPlot::new(
-            "Temp2 °C".into(),
+            "Temp2".into(),
             plot_points_from_log_entry(entries, |e| e.timestamp_ns(), |e| e.temp2.into()),
             ExpectedPlotRange::OneToOneHundred,
-        ),
+        )
+        .with_unit("°C"),
         RawPlot::new(
             "I_in".into(),
             plot_points_from_log_entry(entries, |e| e.timestamp_ns(), |e| e.i_in.into()),
@@ -196,15 +198,17 @@ fn build_all_plots(entries: &[GeneratorLogEntry]) -> Vec<RawPlot> {
             ExpectedPlotRange::OneToOneHundred,
         ),
         RawPlot::new(
-            "Vbat [V]".into(),
+            "Vbat".into(),
             plot_points_from_log_entry(entries, |e| e.timestamp_ns(), |e| e.vbat.into()),
             ExpectedPlotRange::OneToOneHundred,
-        ),
+        )
+        .with_unit("V"),
         RawPlot::new(
-            "Vout [V]".into(),
+            "Vout".into(),
             plot_points_from_log_entry(entries, |e| e.timestamp_ns(), |e| e.vout.into()),
             ExpectedPlotRange::OneToOneHundred,
-        ),
+        )
+        .with_unit("V"),
     ]
 }
 
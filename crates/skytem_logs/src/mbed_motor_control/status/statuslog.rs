@@ -142,11 +142,14 @@ impl StatusLog {
     ) -> Vec<RawPlot> {
         let mut raw_plots = vec![];
         if !engine_temp.is_empty() {
-            raw_plots.push(RawPlot::new(
-                "Engine Temp °C".into(),
-                engine_temp,
-                ExpectedPlotRange::OneToOneHundred,
-            ));
+            raw_plots.push(
+                RawPlot::new(
+                    "Engine Temp".into(),
+                    engine_temp,
+                    ExpectedPlotRange::OneToOneHundred,
+                )
+                .with_unit("°C"),
+            );
         }
         if !fan_on.is_empty() {
             raw_plots.push(RawPlot::new(
@@ -156,11 +159,10 @@ impl StatusLog {
             ));
         }
         if !vbat.is_empty() {
-            raw_plots.push(RawPlot::new(
-                "Vbat [V]".into(),
-                vbat,
-                ExpectedPlotRange::OneToOneHundred,
-            ));
+            raw_plots.push(
+                RawPlot::new("Vbat".into(), vbat, ExpectedPlotRange::OneToOneHundred)
+                    .with_unit("V"),
+            );
         }
         if !setpoint.is_empty() {
             raw_plots.push(RawPlot::new(
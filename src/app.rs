@@ -8,12 +8,16 @@ use egui_phosphor::regular;
 use log_if::prelude::Plotable;
 
 use file_dialog as fd;
-use supported_formats::{LoadedFiles, SupportedFormat};
+use supported_formats::{LoadedFile, LoadedFiles, RegisteredFormat};
 
 mod dropped_files;
 mod file_dialog;
+#[cfg(not(target_arch = "wasm32"))]
+mod load_failures;
 
 pub mod supported_formats;
+#[cfg(not(target_arch = "wasm32"))]
+mod url_download;
 mod util;
 
 /// if a log is loaded from content that exceeds this many unparsed bytes:
@@ -44,6 +48,15 @@ pub struct App {
     #[cfg(not(target_arch = "wasm32"))]
     #[serde(skip)]
     native_file_dialog: fd::native::NativeFileDialog,
+
+    // Loaded from its own file when the app is created, so it is kept even if the app crashes
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    load_failures: load_failures::LoadFailureHistory,
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    url_downloader: url_download::UrlDownloader,
 }
 
 impl Default for App {
@@ -61,6 +74,12 @@ impl Default for App {
 
             #[cfg(not(target_arch = "wasm32"))]
             native_file_dialog: fd::native::NativeFileDialog::default(),
+
+            #[cfg(not(target_arch = "wasm32"))]
+            load_failures: load_failures::LoadFailureHistory::default(),
+
+            #[cfg(not(target_arch = "wasm32"))]
+            url_downloader: url_download::UrlDownloader::default(),
         }
     }
 }
@@ -70,19 +89,59 @@ impl App {
 
     /// Called once before the first frame.
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        Self::add_fonts(&cc.egui_ctx);
+
+        // Load previous app state (if any).
+        // Note that you must enable the `persistence` feature for this to work.
+        #[allow(
+            unused_mut,
+            reason = "Only the native app has load failures to restore"
+        )]
+        let mut app: Self = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, eframe::APP_KEY))
+            .unwrap_or_default();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            app.load_failures = load_failures::LoadFailureHistory::load();
+        }
+        app
+    }
+
+    /// Creates the app with `plotables` already loaded, e.g. for embedding the plots in another application
+    /// with data that is not loaded from files.
+    ///
+    /// The previous app state is not restored, but more files can still be loaded through the UI.
+    pub fn with_plotables(
+        cc: &eframe::CreationContext<'_>,
+        plotables: &[Box<dyn Plotable>],
+    ) -> Self {
+        Self::add_fonts(&cc.egui_ctx);
+        Self::from_plotables(plotables)
+    }
+
+    fn from_plotables(plotables: &[Box<dyn Plotable>]) -> Self {
+        let mut app = Self::default();
+        for plotable in plotables {
+            app.plot.add_plotable(plotable.as_ref());
+        }
+        app
+    }
+
+    /// Registers a log format, e.g. one that is defined in another crate.
+    ///
+    /// Files and archive entries are tried as the built-in formats first, then as the registered formats in the order they were registered.
+    pub fn register_format(&mut self, format: RegisteredFormat) {
+        self.loaded_files.register_format(format);
+    }
+
+    fn add_fonts(ctx: &egui::Context) {
         // This is also where you can customize the look and feel of egui using
         // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
         let mut fonts = egui::FontDefinitions::default();
         egui_phosphor::add_to_fonts(&mut fonts, egui_phosphor::Variant::Regular);
 
-        cc.egui_ctx.set_fonts(fonts);
-
-        // Load previous app state (if any).
-        // Note that you must enable the `persistence` feature for this to work.
-        if let Some(storage) = cc.storage {
-            return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-        }
-        Default::default()
+        ctx.set_fonts(fonts);
     }
 
     fn configure_text_styles(ctx: &egui::Context, font_size: f32) {
@@ -117,6 +176,13 @@ impl eframe::App for App {
         {
             self.error_message = Some(e.to_string());
         }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(e) = self
+            .url_downloader
+            .poll_downloaded_file(&mut self.loaded_files)
+        {
+            self.error_message = Some(e.to_string());
+        }
 
         if !self.font_size_init {
             Self::configure_text_styles(ctx, self.font_size);
@@ -124,25 +190,7 @@ impl eframe::App for App {
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
-                if ui
-                    .button(RichText::new(format!(
-                        "{} Reset",
-                        egui_phosphor::regular::TRASH
-                    )))
-                    .clicked()
-                {
-                    if self.plot.plot_count() == 0 {
-                        self.toasts
-                            .warning("No loaded plots...")
-                            .duration(Some(std::time::Duration::from_secs(3)));
-                    } else {
-                        self.toasts
-                            .info("All loaded logs removed...")
-                            .duration(Some(std::time::Duration::from_secs(3)));
-                    }
-                    self.loaded_files = LoadedFiles::default();
-                    self.plot = LogPlotUi::default();
-                }
+                self.show_reset_button(ui);
                 if ui
                     .button(RichText::new(format!(
                         "{} Open File",
@@ -155,6 +203,8 @@ impl eframe::App for App {
                     #[cfg(not(target_arch = "wasm32"))]
                     self.native_file_dialog.open();
                 }
+                #[cfg(not(target_arch = "wasm32"))]
+                self.url_downloader.show(ui);
                 ui.label(RichText::new(regular::TEXT_T));
                 if ui
                     .add(
@@ -177,14 +227,18 @@ impl eframe::App for App {
                 if cfg!(target_arch = "wasm32") {
                     ui.label(format!("Plotinator3000 v{}", env!("CARGO_PKG_VERSION")));
                 }
+                self.plot.show_data_usage(ui);
+                #[cfg(not(target_arch = "wasm32"))]
+                self.load_failures.show(ui);
                 collapsible_instructions(ui);
             });
         });
 
         egui::CentralPanel::default().show(ctx, |ui| {
             notify_if_logs_added(&mut self.toasts, self.loaded_files.loaded());
+            let loaded = self.loaded_files.take_loaded_files();
             self.plot
-                .ui(ui, &self.loaded_files.take_loaded_files(), &mut self.toasts);
+                .ui(ui, &loaded, self.loaded_files.formats(), &mut self.toasts);
             if self.plot.plot_count() == 0 {
                 // Display the message when plots are shown
                 util::draw_empty_state(ui);
@@ -199,11 +253,36 @@ impl eframe::App for App {
                 egui::warn_if_debug_build(ui);
             });
         });
+        #[cfg(not(target_arch = "wasm32"))]
+        self.load_failures.record(self.loaded_files.take_failures());
         self.toasts.show(ctx);
     }
 }
 
 impl App {
+    fn show_reset_button(&mut self, ui: &mut egui::Ui) {
+        if ui
+            .button(RichText::new(format!(
+                "{} Reset",
+                egui_phosphor::regular::TRASH
+            )))
+            .clicked()
+        {
+            if self.plot.plot_count() == 0 {
+                self.toasts
+                    .warning("No loaded plots...")
+                    .duration(Some(std::time::Duration::from_secs(3)));
+            } else {
+                self.toasts
+                    .info("All loaded logs removed...")
+                    .duration(Some(std::time::Duration::from_secs(3)));
+            }
+            // The registered formats are kept
+            self.loaded_files.clear();
+            self.plot = LogPlotUi::default();
+        }
+    }
+
     fn show_error(&mut self, ui: &egui::Ui) {
         if let Some(error) = self.error_message.clone() {
             egui::Window::new(RichText::new("⚠").size(40.0).color(Color32::RED))
@@ -254,10 +333,10 @@ fn collapsible_instructions(ui: &mut egui::Ui) {
 }
 
 /// Displays a toasts notification if logs are added with the names of all added logs
-fn notify_if_logs_added(toasts: &mut Toasts, logs: &[SupportedFormat]) {
+fn notify_if_logs_added(toasts: &mut Toasts, logs: &[LoadedFile]) {
     if !logs.is_empty() {
         let mut log_names_str = String::new();
-        for l in logs {
+        for l in logs.iter().map(LoadedFile::format) {
             log_names_str.push('\n');
             log_names_str.push('\t');
             log_names_str.push_str(l.descriptive_name());
@@ -269,7 +348,7 @@ fn notify_if_logs_added(toasts: &mut Toasts, logs: &[SupportedFormat]) {
                 if logs.len() == 1 { "" } else { "s" }
             ))
             .duration(Some(Duration::from_secs(2)));
-        for l in logs {
+        for l in logs.iter().map(LoadedFile::format) {
             if let Some(parse_info) = l.parse_info() {
                 log::debug!(
                     "Unparsed bytes for {remainder}:{log_name}",
@@ -303,3 +382,34 @@ fn show_theme_toggle_buttons(ui: &mut egui::Ui) {
 
     ui.ctx().set_theme(theme_preference);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log_if::prelude::Parseable;
+    use skytem_logs::mbed_motor_control::status::statuslog::StatusLog;
+    use testresult::TestResult;
+
+    const TEST_DATA_STATUS: &str =
+        "test_data/mbed_motor_control/v1/20240926_121708/status_20240926_121708_00.bin";
+
+    #[test]
+    fn test_app_from_plotables_has_plots() -> TestResult {
+        let data = std::fs::read(TEST_DATA_STATUS)?;
+        let (status_log, _bytes_read) = StatusLog::from_reader(&mut data.as_slice())?;
+        let plotables: Vec<Box<dyn Plotable>> = vec![Box::new(status_log)];
+
+        let app = App::from_plotables(&plotables);
+        assert!(app.plot.plot_count() > 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_restore_state_of_v1_6_4() -> TestResult {
+        // Saved by v1.6.4 with a status log loaded, new fields have to be optional for it to be restored
+        let state = std::fs::read_to_string("test_data/app_state/v1_6_4.ron")?;
+        let app: App = ron::from_str(&state)?;
+        assert_eq!(app.plot.plot_count(), 5);
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use egui::{Color32, RichText};
+use egui_phosphor::regular;
+use serde::{Deserialize, Serialize};
+
+use super::supported_formats::LoadFailure;
+use crate::APP_NAME;
+
+const FAILURES_FILE: &str = "load_failures.json";
+// The oldest failures are dropped when there are more than this
+const MAX_FAILURES: usize = 100;
+
+/// The files that failed to load, including in previous sessions, stored as JSON in the storage directory of the app (see [`eframe::storage_dir`]).
+///
+/// They are saved as soon as they are recorded so they are kept even if the app crashes.
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) struct LoadFailureHistory {
+    failures: Vec<LoadFailure>,
+    #[serde(skip)]
+    show: bool,
+}
+
+impl LoadFailureHistory {
+    fn path() -> Option<PathBuf> {
+        eframe::storage_dir(APP_NAME).map(|dir| dir.join(FAILURES_FILE))
+    }
+
+    /// Loads the failures of previous sessions, no failures are returned if there's no file or it can't be read
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        match Self::load_from(&path) {
+            Ok(history) => history,
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    log::warn!("Failed loading '{}': {e}", path.display());
+                }
+                Self::default()
+            }
+        }
+    }
+
+    /// Adds the `failures` to the history and saves it
+    pub fn record(&mut self, failures: Vec<LoadFailure>) {
+        if failures.is_empty() {
+            return;
+        }
+        self.add(failures);
+        self.save();
+    }
+
+    fn add(&mut self, failures: Vec<LoadFailure>) {
+        self.failures.extend(failures);
+        let excess = self.failures.len().saturating_sub(MAX_FAILURES);
+        self.failures.drain(..excess);
+    }
+
+    fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+        if let Err(e) = self.save_to(&path) {
+            log::warn!("Failed saving '{}': {e}", path.display());
+        }
+    }
+
+    fn load_from(path: &Path) -> io::Result<Self> {
+        let contents = fs::read(path)?;
+        serde_json::from_slice(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = serde_json::to_vec_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+
+    /// Shows a button that opens a window with the failures, if there are any
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        if self.failures.is_empty() {
+            return;
+        }
+        let title = format!(
+            "{} Load failures ({})",
+            regular::WARNING,
+            self.failures.len()
+        );
+        ui.toggle_value(&mut self.show, RichText::new(&title).color(Color32::YELLOW))
+            .on_hover_text("Files that failed to load, including in previous sessions");
+        if !self.show {
+            return;
+        }
+        let mut open = self.show;
+        let mut clear = false;
+        egui::Window::new(title)
+            .open(&mut open)
+            .show(ui.ctx(), |ui| {
+                if ui.button(format!("{} Clear", regular::TRASH)).clicked() {
+                    clear = true;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("load_failures")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            // Newest first
+                            for failure in self.failures.iter().rev() {
+                                ui.label(failure.time.format("%Y-%m-%d %H:%M:%S").to_string());
+                                ui.label(failure.path.display().to_string());
+                                ui.label(&failure.error);
+                                if ui
+                                    .button(regular::COPY)
+                                    .on_hover_text("Copy the path and error")
+                                    .clicked()
+                                {
+                                    ui.ctx().copy_text(format!(
+                                        "{}: {}",
+                                        failure.path.display(),
+                                        failure.error
+                                    ));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.show = open;
+        if clear {
+            self.failures.clear();
+            self.show = false;
+            self.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use testresult::TestResult;
+
+    fn failure(path: &str) -> LoadFailure {
+        LoadFailure {
+            path: PathBuf::from(path),
+            error: "Unrecognized format".to_owned(),
+            time: chrono::DateTime::from_timestamp(1_727_353_028, 0).expect("valid timestamp"),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() -> TestResult {
+        let tmp_dir = tempdir()?;
+        let path = tmp_dir.path().join(FAILURES_FILE);
+        let history = LoadFailureHistory {
+            failures: vec![failure("a.bin"), failure("b.bin")],
+            show: false,
+        };
+        history.save_to(&path)?;
+        assert_eq!(LoadFailureHistory::load_from(&path)?, history);
+        Ok(())
+    }
+
+    #[test]
+    fn test_oldest_failures_are_dropped() {
+        let mut history = LoadFailureHistory::default();
+        history.add(
+            (0..MAX_FAILURES + 5)
+                .map(|i| failure(&format!("{i}.bin")))
+                .collect(),
+        );
+        assert_eq!(history.failures.len(), MAX_FAILURES);
+        assert_eq!(history.failures[0].path, PathBuf::from("5.bin"));
+    }
+}
@@ -1,26 +1,29 @@
 use log_if::prelude::*;
-use logs::{
-    parse_info::{ParseInfo, ParsedBytes, TotalBytes},
-    SupportedLog,
-};
+use logs::{parse_info::ParseInfo, SupportedLog};
 use serde::{Deserialize, Serialize};
 use skytem_logs::{
     generator::GeneratorLog,
-    mbed_motor_control::{pid::pidlog::PidLog, status::statuslog::StatusLog},
+    mbed_motor_control::{
+        mbed_header::SIZEOF_UNIQ_DESC, pid::pidlog::PidLog, status::statuslog::StatusLog,
+    },
     navsys::NavSysSps,
 };
 use std::{
     fs,
-    io::{self, BufReader},
-    path::Path,
+    io::{self, BufReader, Read as _, Seek as _, SeekFrom},
+    path::{Path, PathBuf},
 };
 
 #[cfg(feature = "hdf")]
 #[cfg(not(target_arch = "wasm32"))]
 mod hdf;
 pub(crate) mod logs;
+mod registry;
 mod util;
 
+pub use registry::RegisteredFormat;
+use registry::UnrecognizedFormat;
+
 /// Represents a supported format, which can be any of the supported format types.
 ///
 /// This simply serves to encapsulate all the supported format in a single type
@@ -35,6 +38,9 @@ pub enum SupportedFormat {
     #[cfg(not(target_arch = "wasm32"))]
     #[allow(clippy::upper_case_acronyms, reason = "The format is called HDF...")]
     HDF(hdf::SupportedHdfFormat),
+    /// A log of a format registered with [`RegisteredFormat::plotable`], it can't be serialized
+    #[serde(skip)]
+    Registered(registry::RegisteredLog),
 }
 
 impl From<(PidLog, ParseInfo)> for SupportedFormat {
@@ -62,97 +68,81 @@ impl From<(NavSysSps, ParseInfo)> for SupportedFormat {
 }
 
 impl SupportedFormat {
-    /// Attempts to parse a log from raw content.
+    /// Attempts to parse a log from raw content as the first of the `formats` it is valid for.
     ///
     /// This is how content is made available in a browser.
-    fn parse_from_buf(content: &[u8]) -> io::Result<Self> {
+    fn parse_from_buf(formats: &[RegisteredFormat], content: &[u8]) -> io::Result<Self> {
         let total_bytes = content.len();
         log::debug!("Parsing content of length: {total_bytes}");
-        let log: Self = if let Ok((pidlog, read_bytes)) = PidLog::try_from_buf(content) {
-            log::debug!("Read: {read_bytes} bytes");
-            (
-                pidlog,
-                ParseInfo::new(ParsedBytes(read_bytes), TotalBytes(total_bytes)),
-            )
-                .into()
-        } else if let Ok((statuslog, read_bytes)) = StatusLog::try_from_buf(content) {
-            (
-                statuslog,
-                ParseInfo::new(ParsedBytes(read_bytes), TotalBytes(read_bytes)),
-            )
-                .into()
-        } else if let Ok((genlog, read_bytes)) = GeneratorLog::try_from_buf(content) {
-            (
-                genlog,
-                ParseInfo::new(ParsedBytes(read_bytes), TotalBytes(total_bytes)),
-            )
-                .into()
-        } else if let Ok((navsyssps_log, read_bytes)) = NavSysSps::try_from_buf(content) {
-            (
-                navsyssps_log,
-                ParseInfo::new(ParsedBytes(read_bytes), TotalBytes(total_bytes)),
-            )
-                .into()
-        } else {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Unrecognized format",
-            ));
-        };
+        if total_bytes == 0 {
+            return Err(UnrecognizedFormat("Content is empty".to_owned()).into());
+        }
+        let log = registry::parse_buf_as_any(formats, content)?;
         log::debug!("Got: {}", log.descriptive_name());
         Ok(log)
     }
 
-    /// Attempts to parse a log from a file path.
+    /// Attempts to parse a log from a file path as the first of the `formats` that the start of the file is valid for.
     ///
     /// This is how it is made available on native.
-    fn parse_from_path(path: &Path) -> io::Result<Self> {
-        let file = fs::File::open(path)?;
-        let total_bytes = file.metadata()?.len() as usize;
-        log::debug!("Parsing content of length: {total_bytes}");
-
-        let mut reader = BufReader::new(file);
-        let log: Self = if util::path_has_hdf_extension(path) {
-            Self::parse_hdf_from_path(path)?
-        } else if PidLog::file_is_valid(path) {
-            let (log, parsed_bytes) = PidLog::from_reader(&mut reader)?;
-            log::debug!("Read: {parsed_bytes} bytes");
-            (
-                log,
-                ParseInfo::new(ParsedBytes(parsed_bytes), TotalBytes(total_bytes)),
-            )
-                .into()
-        } else if StatusLog::file_is_valid(path) {
-            let (log, parsed_bytes) = StatusLog::from_reader(&mut reader)?;
-            (
-                log,
-                ParseInfo::new(ParsedBytes(parsed_bytes), TotalBytes(total_bytes)),
-            )
-                .into()
-        } else if GeneratorLog::file_is_generator_log(path).unwrap_or(false) {
-            let (log, parsed_bytes) = GeneratorLog::from_reader(&mut reader)?;
-            (
-                log,
-                ParseInfo::new(ParsedBytes(parsed_bytes), TotalBytes(total_bytes)),
-            )
-                .into()
-        } else if NavSysSps::file_is_valid(path) {
-            let (log, parsed_bytes) = NavSysSps::from_reader(&mut reader)?;
-            (
-                log,
-                ParseInfo::new(ParsedBytes(parsed_bytes), TotalBytes(total_bytes)),
-            )
-                .into()
-        } else {
+    pub(crate) fn parse_from_path(formats: &[RegisteredFormat], path: &Path) -> io::Result<Self> {
+        // The entries of tar archives are parsed by `LoadedFiles`
+        if util::path_is_tar_archive(path) {
             return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "Unrecognized format",
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is a tar archive and not a single log", path.display()),
             ));
+        }
+        if util::path_has_gzip_extension(path) {
+            return Self::parse_gzip_from_path(formats, path);
+        }
+        let mut file = util::open_file(path)?;
+        let total_bytes = file.metadata()?.len() as usize;
+        log::debug!("Parsing content of length: {total_bytes}");
+        // Reported early so an empty file doesn't end up as a more confusing error from one of the parsers
+        if total_bytes == 0 {
+            return Err(UnrecognizedFormat(format!("'{}' is empty", path.display())).into());
+        }
+        if util::path_has_hdf_extension(path) {
+            return Self::parse_hdf_from_path(path);
+        }
+        let mut probe = Vec::new();
+        (&file)
+            .take(registry::PROBE_BYTES)
+            .read_to_end(&mut probe)?;
+        let Some(format) = formats.iter().find(|f| f.is_buf_valid(&probe)) else {
+            // Smaller than the unique description that starts an Mbed log, so it's most likely a log that was cut off while its header was written
+            if total_bytes < SIZEOF_UNIQ_DESC {
+                return Err(UnrecognizedFormat(format!(
+                    "'{}' is only {total_bytes} bytes, which is too small for the header of a log",
+                    path.display()
+                ))
+                .into());
+            }
+            return Err(registry::unrecognized_format_error());
         };
+        file.seek(SeekFrom::Start(0))?;
+        let log = format.parse_reader(&mut BufReader::new(file), total_bytes)?;
         log::debug!("Got: {}", log.descriptive_name());
         Ok(log)
     }
 
+    /// Decompresses a gzip compressed file and parses its content
+    fn parse_gzip_from_path(formats: &[RegisteredFormat], path: &Path) -> io::Result<Self> {
+        let file = util::open_file(path)?;
+        let contents = util::read_to_end_limited(
+            flate2::read::MultiGzDecoder::new(file),
+            util::MAX_DECOMPRESSED_BYTES,
+        )
+        .map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed decompressing '{}': {e}", path.display()),
+            )
+        })?;
+        Self::parse_from_buf(formats, &contents)
+    }
+
     #[cfg(feature = "hdf")]
     #[cfg(not(target_arch = "wasm32"))]
     fn parse_hdf_from_path(path: &Path) -> io::Result<Self> {
@@ -188,6 +178,22 @@ impl SupportedFormat {
         ))
     }
 
+    /// Parses the file at `path` again, e.g. after it has been written to since it was loaded.
+    ///
+    /// Fails with [`io::ErrorKind::NotFound`] and a message saying so if the file has been moved or deleted.
+    pub(crate) fn reload_from_path(formats: &[RegisteredFormat], path: &Path) -> io::Result<Self> {
+        if !path.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "'{}' no longer exists, it may have been moved or deleted",
+                    path.display()
+                ),
+            ));
+        }
+        Self::parse_from_path(formats, path)
+    }
+
     /// Returns [`None`] if there's no meaningful parsing information such as with HDF5 files.
     #[allow(
         clippy::unnecessary_wraps,
@@ -199,6 +205,7 @@ impl SupportedFormat {
             #[cfg(feature = "hdf")]
             #[cfg(not(target_arch = "wasm32"))]
             Self::HDF(_) => None,
+            Self::Registered(l) => Some(l.parse_info()),
         }
     }
 }
@@ -211,6 +218,7 @@ impl Plotable for SupportedFormat {
             #[cfg(feature = "hdf")]
             #[cfg(not(target_arch = "wasm32"))]
             Self::HDF(hdf) => hdf.raw_plots(),
+            Self::Registered(l) => l.raw_plots(),
         }
     }
 
@@ -220,6 +228,7 @@ impl Plotable for SupportedFormat {
             #[cfg(feature = "hdf")]
             #[cfg(not(target_arch = "wasm32"))]
             Self::HDF(hdf) => hdf.first_timestamp(),
+            Self::Registered(l) => l.first_timestamp(),
         }
     }
 
@@ -229,6 +238,7 @@ impl Plotable for SupportedFormat {
             #[cfg(feature = "hdf")]
             #[cfg(not(target_arch = "wasm32"))]
             Self::HDF(hdf) => hdf.descriptive_name(),
+            Self::Registered(l) => l.descriptive_name(),
         }
     }
 
@@ -238,6 +248,7 @@ impl Plotable for SupportedFormat {
             #[cfg(feature = "hdf")]
             #[cfg(not(target_arch = "wasm32"))]
             Self::HDF(hdf) => hdf.labels(),
+            Self::Registered(l) => l.labels(),
         }
     }
 
@@ -247,60 +258,171 @@ impl Plotable for SupportedFormat {
             #[cfg(feature = "hdf")]
             #[cfg(not(target_arch = "wasm32"))]
             Self::HDF(hdf) => hdf.metadata(),
+            Self::Registered(l) => l.metadata(),
+        }
+    }
+}
+
+/// A supported format and the path of the file it was parsed from, if it was parsed from a file on disk.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LoadedFile {
+    format: SupportedFormat,
+    source_path: Option<PathBuf>,
+}
+
+impl LoadedFile {
+    pub fn format(&self) -> &SupportedFormat {
+        &self.format
+    }
+
+    /// The path of the file the format was parsed from, [`None`] if it was parsed from a buffer or an entry in a zip archive
+    pub fn source_path(&self) -> Option<&Path> {
+        self.source_path.as_deref()
+    }
+}
+
+/// A file that could not be loaded and why.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LoadFailure {
+    pub path: PathBuf,
+    pub error: String,
+    pub time: chrono::DateTime<chrono::Utc>,
+}
+
+impl LoadFailure {
+    fn new(path: &Path, error: &io::Error) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            error: error.to_string(),
+            time: chrono::Utc::now(),
         }
     }
 }
 
 /// Contains all supported logs in a single vector.
-#[derive(Default, Deserialize, Serialize)]
+#[derive(Deserialize, Serialize)]
 pub struct LoadedFiles {
-    loaded: Vec<SupportedFormat>,
+    // Not persisted as they are taken every frame, and logs of registered formats can't be serialized
+    #[serde(skip)]
+    loaded: Vec<LoadedFile>,
+    #[serde(skip)]
+    failures: Vec<LoadFailure>,
+    // The formats that are detected in the content of loaded files, in the order they are tried
+    #[serde(skip, default = "registry::default_formats")]
+    formats: Vec<RegisteredFormat>,
+}
+
+impl Default for LoadedFiles {
+    fn default() -> Self {
+        Self {
+            loaded: Vec::new(),
+            failures: Vec::new(),
+            formats: registry::default_formats(),
+        }
+    }
 }
 
 impl LoadedFiles {
     /// Return a vector of immutable references to all logs
-    pub(crate) fn loaded(&self) -> &[SupportedFormat] {
+    pub(crate) fn loaded(&self) -> &[LoadedFile] {
         &self.loaded
     }
 
     /// Take all the `loaded_files` currently stored and return them as a list
-    pub(crate) fn take_loaded_files(&mut self) -> Vec<SupportedFormat> {
+    pub(crate) fn take_loaded_files(&mut self) -> Vec<LoadedFile> {
         self.loaded.drain(..).collect()
     }
 
+    /// The formats that are detected in the content of loaded files, in the order they are tried
+    pub(crate) fn formats(&self) -> &[RegisteredFormat] {
+        &self.formats
+    }
+
+    /// Removes the loaded files and failures, the registered formats are kept
+    pub(crate) fn clear(&mut self) {
+        self.loaded.clear();
+        self.failures.clear();
+    }
+
+    /// Registers a format that is tried after the formats that are already registered
+    pub(crate) fn register_format(&mut self, format: RegisteredFormat) {
+        self.formats.push(format);
+    }
+
+    /// Take the files that failed to load since the last call
+    pub(crate) fn take_failures(&mut self) -> Vec<LoadFailure> {
+        std::mem::take(&mut self.failures)
+    }
+
+    fn push(&mut self, format: SupportedFormat, source_path: Option<PathBuf>) {
+        self.loaded.push(LoadedFile {
+            format,
+            source_path,
+        });
+    }
+
     pub(crate) fn parse_path(&mut self, path: &Path) -> io::Result<()> {
+        let res = self.parse_path_or_dir(path);
+        if let Err(e) = &res {
+            self.failures.push(LoadFailure::new(path, e));
+        }
+        res
+    }
+
+    fn parse_path_or_dir(&mut self, path: &Path) -> io::Result<()> {
         if path.is_dir() {
             self.parse_directory(path)?;
         } else if is_zip_file(path) {
             #[cfg(not(target_arch = "wasm32"))]
             self.parse_zip_file(path)?;
+        } else if util::path_is_tar_archive(path) {
+            #[cfg(not(target_arch = "wasm32"))]
+            self.parse_tar_file(path)?;
         } else {
-            self.loaded.push(SupportedFormat::parse_from_path(path)?);
+            self.push(
+                SupportedFormat::parse_from_path(&self.formats, path)?,
+                Some(path.to_path_buf()),
+            );
         }
         Ok(())
     }
 
     pub(crate) fn parse_raw_buffer(&mut self, buf: &[u8]) -> io::Result<()> {
-        self.loaded.push(SupportedFormat::parse_from_buf(buf)?);
+        self.push(SupportedFormat::parse_from_buf(&self.formats, buf)?, None);
         Ok(())
     }
 
+    /// Parses every file in the directory and its subdirectories, a file that fails to load is recorded and the rest are still loaded
     fn parse_directory(&mut self, path: &Path) -> io::Result<()> {
         for entry in fs::read_dir(path)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_dir() {
-                if let Err(e) = self.parse_directory(&path) {
-                    log::warn!("{e}");
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    self.push_failure(path, &e);
+                    continue;
                 }
+            };
+            let path = entry.path();
+            let res = if path.is_dir() {
+                self.parse_directory(&path)
             } else if is_zip_file(&path) {
                 #[cfg(not(target_arch = "wasm32"))]
-                self.parse_zip_file(&path)?;
+                let res = self.parse_zip_file(&path);
+                #[cfg(target_arch = "wasm32")]
+                let res = Ok(());
+                res
+            } else if util::path_is_tar_archive(&path) {
+                #[cfg(not(target_arch = "wasm32"))]
+                let res = self.parse_tar_file(&path);
+                #[cfg(target_arch = "wasm32")]
+                let res = Ok(());
+                res
             } else {
-                match SupportedFormat::parse_from_path(&path) {
-                    Ok(l) => self.loaded.push(l),
-                    Err(e) => log::warn!("{e}"),
-                }
+                SupportedFormat::parse_from_path(&self.formats, &path)
+                    .map(|l| self.push(l, Some(path.clone())))
+            };
+            if let Err(e) = res {
+                self.push_failure(&path, &e);
             }
         }
         Ok(())
@@ -308,23 +430,149 @@ impl LoadedFiles {
 
     #[cfg(not(target_arch = "wasm32"))]
     fn parse_zip_file(&mut self, path: &Path) -> io::Result<()> {
-        let file = fs::File::open(path)?;
-        let mut archive = zip::ZipArchive::new(file)?;
-
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)?;
-            if file.is_file() {
-                let mut contents = Vec::new();
-                io::Read::read_to_end(&mut file, &mut contents)?;
-                if let Ok(log) = SupportedFormat::parse_from_buf(&contents) {
-                    self.loaded.push(log);
+        let entry_count = zip::ZipArchive::new(fs::File::open(path)?)?.len();
+        let worker_count = std::thread::available_parallelism()
+            .map_or(1, std::num::NonZeroUsize::get)
+            .clamp(1, entry_count.max(1));
+        let formats = &self.formats;
+        // `ZipArchive::by_index` needs `&mut` so each worker opens the archive and extracts and parses every `worker_count`th entry
+        let worker_results: Vec<io::Result<Vec<ParsedZipEntry>>> = std::thread::scope(|s| {
+            let workers: Vec<_> = (0..worker_count)
+                .map(|worker| {
+                    s.spawn(move || {
+                        parse_zip_entries(
+                            formats,
+                            path,
+                            (worker..entry_count).step_by(worker_count),
+                        )
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|w| {
+                    w.join().unwrap_or_else(|_| {
+                        Err(io::Error::other(
+                            "Parsing the entries of the archive panicked",
+                        ))
+                    })
+                })
+                .collect()
+        });
+        let mut parsed = Vec::with_capacity(entry_count);
+        let mut worker_err = None;
+        for res in worker_results {
+            match res {
+                Ok(entries) => parsed.extend(entries),
+                Err(e) => worker_err = Some(e),
+            }
+        }
+        // The entries of the other workers are still loaded, the failure is recorded once as all workers fail the same way if the archive can't be opened
+        if let Some(e) = worker_err {
+            self.push_failure(path, &e);
+        }
+        // Keep the order of the entries in the archive
+        parsed.sort_unstable_by_key(|(idx, ..)| *idx);
+        for (_, entry_path, res) in parsed {
+            self.push_archive_entry(&entry_path, res);
+        }
+        Ok(())
+    }
+
+    /// Adds the log parsed from an archive entry, or records the failure with the path of the entry inside the archive, e.g. `logs.zip/pid.bin`
+    #[cfg(not(target_arch = "wasm32"))]
+    fn push_archive_entry(&mut self, entry_path: &Path, res: io::Result<SupportedFormat>) {
+        match res {
+            Ok(log) => self.push(log, None),
+            Err(e) => self.push_failure(entry_path, &e),
+        }
+    }
+
+    /// Records the failure of a file found in a directory or archive.
+    ///
+    /// Files that none of the formats recognize are skipped without recording them, as they are most likely not logs, e.g. a readme or images.
+    fn push_failure(&mut self, path: &Path, e: &io::Error) {
+        if registry::is_unrecognized_format(e) {
+            log::info!("Skipping {}: {e}", path.display());
+            return;
+        }
+        log::warn!("{}: {e}", path.display());
+        self.failures.push(LoadFailure::new(path, e));
+    }
+
+    /// Parses the entries of a tar archive, gzip compressed if it has a `.gz` or `.tgz` extension.
+    ///
+    /// Unlike a zip archive, the entries can only be read in order, so they are extracted and parsed one at a time.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn parse_tar_file(&mut self, path: &Path) -> io::Result<()> {
+        let file = util::open_file(path)?;
+        let is_compressed = util::path_has_gzip_extension(path)
+            || path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("tgz"));
+        let reader: Box<dyn io::Read> = if is_compressed {
+            Box::new(flate2::read::MultiGzDecoder::new(BufReader::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries()? {
+            // The entries after a broken header can't be found, but the ones before it are still loaded
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    self.push_failure(path, &e);
+                    break;
                 }
+            };
+            if entry.header().entry_type().is_file() {
+                let entry_path = path.join(&*String::from_utf8_lossy(&entry.path_bytes()));
+                let res = parse_archive_entry(&self.formats, entry);
+                self.push_archive_entry(&entry_path, res);
             }
         }
         Ok(())
     }
 }
 
+/// The index of a zip entry, its path inside the archive and the log parsed from it
+#[cfg(not(target_arch = "wasm32"))]
+type ParsedZipEntry = (usize, PathBuf, io::Result<SupportedFormat>);
+
+/// Extracts and parses the zip entries with the given `indices`, returning the result of extracting and parsing each file entry.
+///
+/// Fails if the archive itself can't be opened.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_zip_entries(
+    formats: &[RegisteredFormat],
+    path: &Path,
+    indices: impl Iterator<Item = usize>,
+) -> io::Result<Vec<ParsedZipEntry>> {
+    let mut archive = zip::ZipArchive::new(fs::File::open(path)?)?;
+    let mut parsed = Vec::new();
+    for i in indices {
+        let entry_path = path.join(archive.name_for_index(i).unwrap_or_default());
+        match archive.by_index(i) {
+            Ok(file) if file.is_file() => {
+                parsed.push((i, entry_path, parse_archive_entry(formats, file)));
+            }
+            Ok(_) => {}
+            Err(e) => parsed.push((i, entry_path, Err(e.into()))),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Extracts and parses an entry of a zip or tar archive.
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_archive_entry(
+    formats: &[RegisteredFormat],
+    entry: impl io::Read,
+) -> io::Result<SupportedFormat> {
+    let contents = util::read_to_end_limited(entry, util::MAX_DECOMPRESSED_BYTES)?;
+    SupportedFormat::parse_from_buf(formats, &contents)
+}
+
 fn is_zip_file(path: &Path) -> bool {
     path.extension()
         .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
@@ -351,4 +599,393 @@ mod tests {
         let v: Vec<Box<dyn Plotable>> = vec![Box::new(status_log), Box::new(pidlog)];
         assert_eq!(v.len(), 2);
     }
+
+    #[test]
+    fn test_empty_file_is_rejected() -> testresult::TestResult {
+        let file = tempfile::NamedTempFile::new()?;
+        let Err(e) = SupportedFormat::parse_from_path(&registry::LOG_FORMATS, file.path()) else {
+            panic!("Expected an empty file to be rejected");
+        };
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(e.to_string().ends_with("is empty"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_truncated_header_is_rejected() -> testresult::TestResult {
+        let tmp_dir = tempfile::tempdir()?;
+        let truncated = tmp_dir.path().join("status.bin");
+        fs::write(
+            &truncated,
+            &fs::read(TEST_DATA_STATUS)?[..SIZEOF_UNIQ_DESC / 2],
+        )?;
+        let Err(e) = SupportedFormat::parse_from_path(&registry::LOG_FORMATS, &truncated) else {
+            panic!("Expected a file with half a header to be rejected");
+        };
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert!(e.to_string().ends_with("too small for the header of a log"));
+
+        // A generator log with a single short entry is smaller than a header but still a log
+        let short_gen_log = tmp_dir.path().join("gen.log");
+        fs::write(
+            &short_gen_log,
+            "20230124_134745 Vout: 7.3 Vbat: 0.1 Iout: 0.0 RPM: 60 Load: 1.2 PWM: 1.2 Temp1 6.9 Temp2 8.4 IIn: 8.8 Irotor: 0.7 Rrotor: 1.2\n",
+        )?;
+        assert!(fs::metadata(&short_gen_log)?.len() < SIZEOF_UNIQ_DESC as u64);
+        let log = SupportedFormat::parse_from_path(&registry::LOG_FORMATS, &short_gen_log)?;
+        assert!(matches!(
+            log,
+            SupportedFormat::Log(SupportedLog::Generator(..))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_of_detected_format_is_returned() -> testresult::TestResult {
+        let tmp_dir = tempfile::tempdir()?;
+        let truncated = tmp_dir.path().join("status.bin");
+        // Detected as a status log by its unique description, but cut off in the rest of the header
+        fs::write(
+            &truncated,
+            &fs::read(TEST_DATA_STATUS)?[..SIZEOF_UNIQ_DESC + 10],
+        )?;
+        let Err(e) = SupportedFormat::parse_from_path(&registry::LOG_FORMATS, &truncated) else {
+            panic!("Expected a truncated status log to fail parsing");
+        };
+        assert!(
+            e.to_string()
+                .starts_with("Failed parsing as Mbed Status Log"),
+            "{e}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_zip_entries_with_same_name_are_all_parsed() -> testresult::TestResult {
+        use std::io::Write as _;
+        let tmp_dir = tempfile::tempdir()?;
+        let zip_path = tmp_dir.path().join("logs.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path)?);
+        let status_data = fs::read(TEST_DATA_STATUS)?;
+        for dir in ["first", "second"] {
+            zip.start_file(
+                format!("{dir}/status.bin"),
+                zip::write::SimpleFileOptions::default(),
+            )?;
+            zip.write_all(&status_data)?;
+        }
+        zip.finish()?;
+
+        let mut loaded_files = LoadedFiles::default();
+        loaded_files.parse_path(&zip_path)?;
+        assert_eq!(loaded_files.loaded().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_zip_entries_keep_archive_order() -> testresult::TestResult {
+        use std::io::Write as _;
+        let tmp_dir = tempfile::tempdir()?;
+        let zip_path = tmp_dir.path().join("logs.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path)?);
+        let status_data = fs::read(TEST_DATA_STATUS)?;
+        let pid_data = fs::read(TEST_DATA_PID)?;
+        for i in 0..10 {
+            zip.start_file(format!("{i}.bin"), zip::write::SimpleFileOptions::default())?;
+            zip.write_all(if i % 2 == 0 { &status_data } else { &pid_data })?;
+        }
+        zip.start_file("readme.txt", zip::write::SimpleFileOptions::default())?;
+        zip.write_all(b"Not a log")?;
+        zip.finish()?;
+
+        let mut loaded_files = LoadedFiles::default();
+        loaded_files.parse_path(&zip_path)?;
+        let loaded = loaded_files.loaded();
+        assert_eq!(loaded.len(), 10);
+        for (i, l) in loaded.iter().enumerate() {
+            let is_status = matches!(
+                l.format(),
+                SupportedFormat::Log(SupportedLog::MbedStatus(..))
+            );
+            assert_eq!(is_status, i % 2 == 0);
+        }
+        // The readme is not a log so it's skipped rather than recorded as a failure
+        assert!(loaded_files.take_failures().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_zip_entry_failing_to_extract_keeps_other_entries() -> testresult::TestResult {
+        use std::io::Write as _;
+        let tmp_dir = tempfile::tempdir()?;
+        let zip_path = tmp_dir.path().join("logs.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path)?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        let status_data = fs::read(TEST_DATA_STATUS)?;
+        for i in 0..4 {
+            zip.start_file(format!("{i}.bin"), options)?;
+            zip.write_all(&status_data)?;
+        }
+        zip.start_file("corrupt.bin", options)?;
+        zip.write_all(b"CORRUPT_ME")?;
+        zip.write_all(&status_data)?;
+        zip.finish()?;
+        // Changing the stored content makes the checksum of the entry mismatch
+        let mut zip_bytes = fs::read(&zip_path)?;
+        let pos = zip_bytes
+            .windows(10)
+            .position(|w| w == b"CORRUPT_ME")
+            .ok_or("marker not found")?;
+        zip_bytes[pos..pos + 10].copy_from_slice(b"CORRUPTED!");
+        fs::write(&zip_path, zip_bytes)?;
+
+        let mut loaded_files = LoadedFiles::default();
+        loaded_files.parse_path(&zip_path)?;
+        assert_eq!(loaded_files.loaded().len(), 4);
+        let failures = loaded_files.take_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, zip_path.join("corrupt.bin"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_gzip_compressed_file_is_decompressed() -> testresult::TestResult {
+        use std::io::Write as _;
+        let tmp_dir = tempfile::tempdir()?;
+        let gz_path = tmp_dir.path().join("status.bin.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&gz_path)?,
+            flate2::Compression::default(),
+        );
+        encoder.write_all(&fs::read(TEST_DATA_STATUS)?)?;
+        encoder.finish()?;
+
+        let log = SupportedFormat::parse_from_path(&registry::LOG_FORMATS, &gz_path)?;
+        assert!(matches!(
+            log,
+            SupportedFormat::Log(SupportedLog::MbedStatus(..))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tar_archive_is_not_a_single_log() {
+        let Err(e) =
+            SupportedFormat::parse_from_path(&registry::LOG_FORMATS, Path::new("logs.tar.gz"))
+        else {
+            panic!("Expected a tar archive to not be parsed as a single log");
+        };
+        assert!(e.to_string().contains("tar archive"));
+    }
+
+    fn write_tar(writer: impl io::Write) -> io::Result<()> {
+        let mut builder = tar::Builder::new(writer);
+        builder.append_path_with_name(TEST_DATA_STATUS, "status.bin")?;
+        builder.append_path_with_name(TEST_DATA_PID, "pid.bin")?;
+        let readme = b"Not a log";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(readme.len() as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, "readme.txt", readme.as_slice())?;
+        builder.into_inner()?.flush()
+    }
+
+    #[test]
+    fn test_tar_archive_entries_are_parsed() -> testresult::TestResult {
+        use std::io::Write as _;
+        let tmp_dir = tempfile::tempdir()?;
+        let tar_path = tmp_dir.path().join("logs.tar");
+        write_tar(fs::File::create(&tar_path)?)?;
+        let tar_gz_path = tmp_dir.path().join("logs.tar.gz");
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&tar_gz_path)?,
+            flate2::Compression::default(),
+        );
+        write_tar(&mut encoder)?;
+        encoder.finish()?.flush()?;
+
+        for path in [tar_path, tar_gz_path] {
+            let mut loaded_files = LoadedFiles::default();
+            loaded_files.parse_path(&path)?;
+            let loaded = loaded_files.take_loaded_files();
+            assert_eq!(loaded.len(), 2, "{}", path.display());
+            assert!(matches!(
+                loaded[0].format(),
+                SupportedFormat::Log(SupportedLog::MbedStatus(..))
+            ));
+            assert!(matches!(
+                loaded[1].format(),
+                SupportedFormat::Log(SupportedLog::MbedPid(..))
+            ));
+            assert!(loaded_files.take_failures().is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_loaded_file_remembers_source_path() -> testresult::TestResult {
+        let mut loaded_files = LoadedFiles::default();
+        loaded_files.parse_path(Path::new(TEST_DATA_STATUS))?;
+        loaded_files.parse_raw_buffer(&fs::read(TEST_DATA_PID)?)?;
+        let loaded = loaded_files.take_loaded_files();
+        assert_eq!(loaded[0].source_path(), Some(Path::new(TEST_DATA_STATUS)));
+        assert_eq!(loaded[1].source_path(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_failures_are_recorded() -> testresult::TestResult {
+        let tmp_dir = tempfile::tempdir()?;
+        let unknown = tmp_dir.path().join("unknown.bin");
+        // Large enough to not be rejected as too small for a header
+        fs::write(&unknown, b"Not a log. ".repeat(20))?;
+        fs::copy(TEST_DATA_STATUS, tmp_dir.path().join("status.bin"))?;
+        // Detected as a status log but fails to parse
+        let truncated = tmp_dir.path().join("truncated.bin");
+        let status_data = fs::read(TEST_DATA_STATUS)?;
+        fs::write(&truncated, &status_data[..SIZEOF_UNIQ_DESC + 10])?;
+
+        let mut loaded_files = LoadedFiles::default();
+        loaded_files.parse_path(tmp_dir.path())?;
+        // An unrecognized file in a directory is skipped, but it's a failure when it's opened directly
+        let failures = loaded_files.take_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, truncated);
+        assert!(loaded_files.parse_path(&unknown).is_err());
+        assert_eq!(loaded_files.loaded().len(), 1);
+        let failures = loaded_files.take_failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].path, unknown);
+        assert!(failures[0].error.contains("Unrecognized format"));
+        assert!(loaded_files.take_failures().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_broken_archives_in_directory_are_recorded() -> testresult::TestResult {
+        let tmp_dir = tempfile::tempdir()?;
+        fs::copy(TEST_DATA_STATUS, tmp_dir.path().join("status.bin"))?;
+        let zip_path = tmp_dir.path().join("broken.zip");
+        fs::write(&zip_path, b"Not a zip archive")?;
+        // Overwrite the header of the second entry so only the first can be read
+        let tar_path = tmp_dir.path().join("logs.tar");
+        let mut tar_bytes = Vec::new();
+        write_tar(&mut tar_bytes)?;
+        let second_header =
+            512 + fs::metadata(TEST_DATA_STATUS)?.len().div_ceil(512) as usize * 512;
+        tar_bytes[second_header..second_header + 512].fill(b'A');
+        fs::write(&tar_path, tar_bytes)?;
+
+        let mut loaded_files = LoadedFiles::default();
+        loaded_files.parse_path(tmp_dir.path())?;
+        assert_eq!(loaded_files.loaded().len(), 2);
+        let mut failed_paths: Vec<_> = loaded_files
+            .take_failures()
+            .into_iter()
+            .map(|f| f.path)
+            .collect();
+        failed_paths.sort();
+        assert_eq!(failed_paths, [zip_path, tar_path]);
+        Ok(())
+    }
+
+    /// A format that could be defined in another crate, a header line followed by a value per line
+    struct CounterLog {
+        raw_plots: Vec<RawPlot>,
+    }
+
+    impl CounterLog {
+        const HEADER: &'static [u8] = b"COUNTER LOG\n";
+    }
+
+    impl Parseable for CounterLog {
+        const DESCRIPTIVE_NAME: &str = "Counter log";
+
+        fn from_reader(reader: &mut impl io::BufRead) -> io::Result<(Self, usize)> {
+            let mut contents = String::new();
+            let read_bytes = reader.read_to_string(&mut contents)?;
+            let points = contents
+                .lines()
+                .skip(1)
+                .enumerate()
+                .map(|(i, line)| {
+                    let value: f64 = line.trim().parse().map_err(io::Error::other)?;
+                    Ok([i as f64 * 1e9, value])
+                })
+                .collect::<io::Result<_>>()?;
+            let raw_plots = vec![RawPlot::new(
+                "Count".into(),
+                points,
+                ExpectedPlotRange::OneToOneHundred,
+            )];
+            Ok((Self { raw_plots }, read_bytes))
+        }
+
+        fn is_buf_valid(buf: &[u8]) -> bool {
+            buf.starts_with(Self::HEADER)
+        }
+    }
+
+    impl Plotable for CounterLog {
+        fn raw_plots(&self) -> &[RawPlot] {
+            &self.raw_plots
+        }
+
+        fn first_timestamp(&self) -> chrono::DateTime<chrono::Utc> {
+            chrono::DateTime::UNIX_EPOCH
+        }
+
+        fn descriptive_name(&self) -> &str {
+            Self::DESCRIPTIVE_NAME
+        }
+
+        fn labels(&self) -> Option<&[PlotLabels]> {
+            None
+        }
+
+        fn metadata(&self) -> Option<Vec<(String, String)>> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_registered_format_is_loaded() -> testresult::TestResult {
+        let tmp_dir = tempfile::tempdir()?;
+        let path = tmp_dir.path().join("counter.txt");
+        fs::write(&path, b"COUNTER LOG\n1\n2\n3\n")?;
+
+        let mut loaded_files = LoadedFiles::default();
+        assert!(loaded_files.parse_path(&path).is_err());
+        loaded_files.take_failures();
+
+        loaded_files.register_format(RegisteredFormat::plotable::<CounterLog>());
+        loaded_files.parse_path(&path)?;
+        loaded_files.parse_raw_buffer(&fs::read(&path)?)?;
+        // The built-in formats are still detected
+        loaded_files.parse_path(Path::new(TEST_DATA_STATUS))?;
+        let loaded = loaded_files.take_loaded_files();
+        assert_eq!(loaded.len(), 3);
+        for counter_log in &loaded[..2] {
+            assert_eq!(counter_log.format().descriptive_name(), "Counter log");
+            assert_eq!(counter_log.format().raw_plots()[0].points().len(), 3);
+        }
+        assert!(matches!(loaded[2].format(), SupportedFormat::Log(_)));
+
+        // Reloading uses the registered formats as well
+        let reloaded = SupportedFormat::reload_from_path(loaded_files.formats(), &path)?;
+        assert_eq!(reloaded.descriptive_name(), "Counter log");
+        Ok(())
+    }
+
+    #[test]
+    fn test_reload_moved_file() {
+        let Err(e) =
+            SupportedFormat::reload_from_path(&registry::LOG_FORMATS, Path::new("not/a/log.bin"))
+        else {
+            panic!("Expected reloading a missing file to fail");
+        };
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        assert!(e.to_string().contains("moved or deleted"));
+    }
 }
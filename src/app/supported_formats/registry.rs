@@ -0,0 +1,291 @@
+use std::{fmt, io, sync::Arc};
+
+use log_if::prelude::*;
+use skytem_logs::{
+    generator::GeneratorLog,
+    mbed_motor_control::{pid::pidlog::PidLog, status::statuslog::StatusLog},
+    navsys::NavSysSps,
+};
+
+use super::{
+    logs::parse_info::{ParseInfo, ParsedBytes, TotalBytes},
+    SupportedFormat,
+};
+
+/// Files are detected as a format by this many bytes at their start
+pub(crate) const PROBE_BYTES: u64 = 64 * 1024;
+
+/// A format that can be detected in the start of a file or buffer and parsed from a reader.
+///
+/// The built-in formats are always registered. Other crates can add their own formats with
+/// [`RegisteredFormat::plotable`] and [`crate::App::register_format`].
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredFormat {
+    descriptive_name: &'static str,
+    is_buf_valid: fn(&[u8]) -> bool,
+    parse_reader: fn(&mut dyn io::BufRead, usize) -> io::Result<SupportedFormat>,
+}
+
+impl RegisteredFormat {
+    pub(crate) const fn new<P>() -> Self
+    where
+        P: Parseable,
+        SupportedFormat: From<(P, ParseInfo)>,
+    {
+        Self {
+            descriptive_name: P::DESCRIPTIVE_NAME,
+            is_buf_valid: P::is_buf_valid,
+            parse_reader: parse_reader::<P>,
+        }
+    }
+
+    /// A format that is parsed with its [`Parseable`] implementation and plotted with its [`Plotable`] implementation,
+    /// e.g. a format that is defined in another crate.
+    ///
+    /// Files are detected with [`Parseable::is_buf_valid`] on their first 64 KiB.
+    /// Logs of these formats are not restored when the app is restarted.
+    pub const fn plotable<P>() -> Self
+    where
+        P: Parseable + Plotable + Send + Sync + 'static,
+    {
+        Self {
+            descriptive_name: P::DESCRIPTIVE_NAME,
+            is_buf_valid: P::is_buf_valid,
+            parse_reader: parse_plotable_reader::<P>,
+        }
+    }
+
+    pub fn descriptive_name(&self) -> &'static str {
+        self.descriptive_name
+    }
+
+    pub(crate) fn is_buf_valid(&self, buf: &[u8]) -> bool {
+        (self.is_buf_valid)(buf)
+    }
+
+    /// Parses the `total_bytes` of content in `reader` as this format, the error says which format it failed to parse as
+    pub(crate) fn parse_reader(
+        &self,
+        reader: &mut dyn io::BufRead,
+        total_bytes: usize,
+    ) -> io::Result<SupportedFormat> {
+        (self.parse_reader)(reader, total_bytes).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("Failed parsing as {}: {e}", self.descriptive_name),
+            )
+        })
+    }
+}
+
+fn parse_reader<P>(
+    mut reader: &mut dyn io::BufRead,
+    total_bytes: usize,
+) -> io::Result<SupportedFormat>
+where
+    P: Parseable,
+    SupportedFormat: From<(P, ParseInfo)>,
+{
+    let (log, read_bytes) = P::from_reader(&mut reader)?;
+    log::debug!("Read: {read_bytes} bytes");
+    // The total is the size of the content and not what the parser read, so bytes at the end that couldn't be parsed are reported
+    Ok((
+        log,
+        ParseInfo::new(ParsedBytes(read_bytes), TotalBytes(total_bytes)),
+    )
+        .into())
+}
+
+fn parse_plotable_reader<P>(
+    mut reader: &mut dyn io::BufRead,
+    total_bytes: usize,
+) -> io::Result<SupportedFormat>
+where
+    P: Parseable + Plotable + Send + Sync + 'static,
+{
+    let (log, read_bytes) = P::from_reader(&mut reader)?;
+    log::debug!("Read: {read_bytes} bytes");
+    Ok(SupportedFormat::Registered(RegisteredLog {
+        log: Arc::new(log),
+        parse_info: ParseInfo::new(ParsedBytes(read_bytes), TotalBytes(total_bytes)),
+    }))
+}
+
+/// A log of a format that was registered with [`RegisteredFormat::plotable`].
+#[derive(Clone)]
+pub struct RegisteredLog {
+    log: Arc<dyn Plotable + Send + Sync>,
+    parse_info: ParseInfo,
+}
+
+impl fmt::Debug for RegisteredLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisteredLog")
+            .field("descriptive_name", &self.log.descriptive_name())
+            .field("parse_info", &self.parse_info)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RegisteredLog {
+    pub(crate) fn parse_info(&self) -> ParseInfo {
+        self.parse_info
+    }
+}
+
+impl Plotable for RegisteredLog {
+    fn raw_plots(&self) -> &[RawPlot] {
+        self.log.raw_plots()
+    }
+
+    fn first_timestamp(&self) -> chrono::DateTime<chrono::Utc> {
+        self.log.first_timestamp()
+    }
+
+    fn descriptive_name(&self) -> &str {
+        self.log.descriptive_name()
+    }
+
+    fn labels(&self) -> Option<&[PlotLabels]> {
+        self.log.labels()
+    }
+
+    fn metadata(&self) -> Option<Vec<(String, String)>> {
+        self.log.metadata()
+    }
+}
+
+/// The built-in formats that are detected in loaded files, in the order they are tried.
+pub(crate) const LOG_FORMATS: [RegisteredFormat; 4] = [
+    RegisteredFormat::new::<PidLog>(),
+    RegisteredFormat::new::<StatusLog>(),
+    RegisteredFormat::new::<GeneratorLog>(),
+    RegisteredFormat::new::<NavSysSps>(),
+];
+
+/// The formats that are registered when no other formats have been registered
+pub(crate) fn default_formats() -> Vec<RegisteredFormat> {
+    LOG_FORMATS.to_vec()
+}
+
+/// The content is not recognized by any of the formats, as opposed to content that is detected as a format but fails to parse as it
+#[derive(Debug)]
+pub(crate) struct UnrecognizedFormat(pub(crate) String);
+
+impl fmt::Display for UnrecognizedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UnrecognizedFormat {}
+
+impl From<UnrecognizedFormat> for io::Error {
+    fn from(e: UnrecognizedFormat) -> Self {
+        Self::new(io::ErrorKind::InvalidData, e)
+    }
+}
+
+pub(crate) fn unrecognized_format_error() -> io::Error {
+    UnrecognizedFormat("Unrecognized format".to_owned()).into()
+}
+
+pub(crate) fn is_unrecognized_format(e: &io::Error) -> bool {
+    e.get_ref().is_some_and(|e| e.is::<UnrecognizedFormat>())
+}
+
+/// Parses `buf` as the first of the `formats` that it is valid for and that parses it successfully.
+///
+/// If it is valid for some of the `formats` but none of them can parse it, the error of the first of them is returned.
+pub(crate) fn parse_buf_as_any(
+    formats: &[RegisteredFormat],
+    buf: &[u8],
+) -> io::Result<SupportedFormat> {
+    let mut first_err = None;
+    for format in formats.iter().filter(|f| f.is_buf_valid(buf)) {
+        let mut reader = buf;
+        match format.parse_reader(&mut reader, buf.len()) {
+            Ok(log) => return Ok(log),
+            Err(e) => {
+                log::debug!("{e}");
+                first_err.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_err.unwrap_or_else(unrecognized_format_error))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use skytem_logs::mbed_motor_control::mbed_header::SIZEOF_UNIQ_DESC;
+
+    use super::*;
+
+    const TEST_FILES: [&str; 3] = [
+        "test_data/mbed_motor_control/v1/20240926_121708/status_20240926_121708_00.bin",
+        "test_data/mbed_motor_control/v1/20240926_121708/pid_20240926_121708_00.bin",
+        "test_data/generator/20230124_134738_Gen.log",
+    ];
+
+    #[test]
+    fn test_test_files_are_valid_for_exactly_one_format() -> testresult::TestResult {
+        for path in TEST_FILES {
+            let buf = fs::read(path)?;
+            let valid_formats: Vec<&str> = LOG_FORMATS
+                .iter()
+                .filter(|f| f.is_buf_valid(&buf))
+                .map(RegisteredFormat::descriptive_name)
+                .collect();
+            assert_eq!(
+                valid_formats.len(),
+                1,
+                "{path} is valid for {valid_formats:?}"
+            );
+            assert!(parse_buf_as_any(&LOG_FORMATS, &buf).is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unparsed_bytes_of_status_log_are_reported() -> testresult::TestResult {
+        let mut buf = fs::read(TEST_FILES[0])?;
+        let file_len = buf.len();
+        // Not enough bytes for another entry
+        buf.extend_from_slice(&[0xFF; 3]);
+        let log = parse_buf_as_any(&LOG_FORMATS, &buf)?;
+        let parse_info = log.parse_info().expect("A status log has parse info");
+        assert_eq!(parse_info.total_bytes(), buf.len());
+        assert!(parse_info.parsed_bytes() <= file_len);
+        assert!(parse_info.remainder_bytes() >= 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_of_detected_format_is_returned() -> testresult::TestResult {
+        let buf = fs::read(TEST_FILES[0])?;
+        // Only the start of the header, so it is detected as a status log but can't be parsed
+        let truncated = &buf[..SIZEOF_UNIQ_DESC + 10];
+        let Err(e) = parse_buf_as_any(&LOG_FORMATS, truncated) else {
+            panic!("Expected a truncated status log to fail parsing");
+        };
+        assert!(
+            e.to_string()
+                .starts_with("Failed parsing as Mbed Status Log"),
+            "{e}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unregistered_format_is_unrecognized() -> testresult::TestResult {
+        let buf = fs::read(TEST_FILES[0])?;
+        let only_pid = [RegisteredFormat::new::<PidLog>()];
+        let Err(e) = parse_buf_as_any(&only_pid, &buf) else {
+            panic!("Expected a status log to not be parsed as a PID log");
+        };
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::{fs, io, path::Path};
+
 // File extensions we recognize as hdf files.
 const POSSIBLE_HDF_EXTENSIONS_CASE_INSENSITIVE: [&str; 3] = ["h5", "hdf", "hdf5"];
 
@@ -13,3 +15,109 @@ pub fn path_has_hdf_extension(path: &std::path::Path) -> bool {
     }
     false
 }
+
+/// Returns true if the file is gzip compressed by its extension, e.g. `gen.log.gz`
+pub fn path_has_gzip_extension(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("gz"))
+}
+
+/// Decompressed files and entries of archives that are larger than this are not loaded,
+/// so a small compressed file can't expand to fill up the memory
+pub const MAX_DECOMPRESSED_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Reads all of `reader`, fails if there's more than `limit` bytes
+pub fn read_to_end_limited(reader: impl io::Read, limit: u64) -> io::Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    io::Read::read_to_end(&mut reader.take(limit + 1), &mut contents)?;
+    if contents.len() as u64 > limit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "The content is larger than the maximum of {}",
+                crate::util::format_data_size(limit as usize)
+            ),
+        ));
+    }
+    Ok(contents)
+}
+
+/// Returns true if the file is a tar archive (possibly gzip compressed) by its extension, e.g. `logs.tar.gz`
+pub fn path_is_tar_archive(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let file_name = file_name.to_ascii_lowercase();
+    [".tar", ".tar.gz", ".tgz"]
+        .iter()
+        .any(|ext| file_name.ends_with(ext))
+}
+
+// Windows error codes for a file that is opened or locked by another process
+const ERROR_SHARING_VIOLATION: i32 = 32;
+const ERROR_LOCK_VIOLATION: i32 = 33;
+
+fn is_locked_by_other_process(e: &io::Error) -> bool {
+    cfg!(windows)
+        && matches!(
+            e.raw_os_error(),
+            Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+        )
+}
+
+/// Opens the file at `path`, with a message saying that the file is in use if it is locked by another process e.g. because it is still being written.
+///
+/// Files are opened on the UI thread so it isn't retried, the user can load it again when it is no longer in use.
+pub fn open_file(path: &Path) -> io::Result<fs::File> {
+    fs::File::open(path).map_err(|e| {
+        if is_locked_by_other_process(&e) {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "'{}' is in use by another process, try again when it is no longer in use",
+                    path.display()
+                ),
+            )
+        } else {
+            e
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_error_codes_only_on_windows() {
+        let sharing_violation = io::Error::from_raw_os_error(ERROR_SHARING_VIOLATION);
+        assert_eq!(
+            is_locked_by_other_process(&sharing_violation),
+            cfg!(windows)
+        );
+        let not_found = io::Error::from(io::ErrorKind::NotFound);
+        assert!(!is_locked_by_other_process(&not_found));
+    }
+
+    #[test]
+    fn test_archive_extensions() {
+        assert!(path_has_gzip_extension(Path::new("gen.log.gz")));
+        assert!(path_has_gzip_extension(Path::new("STATUS.BIN.GZ")));
+        assert!(!path_has_gzip_extension(Path::new("gen.log")));
+        assert!(path_is_tar_archive(Path::new("logs.tar.gz")));
+        assert!(path_is_tar_archive(Path::new("logs.TGZ")));
+        assert!(path_is_tar_archive(Path::new("logs.tar")));
+        assert!(!path_is_tar_archive(Path::new("gen.log.gz")));
+    }
+
+    #[test]
+    fn test_read_to_end_limited() {
+        let contents = [1, 2, 3, 4];
+        assert_eq!(
+            read_to_end_limited(contents.as_slice(), 4).unwrap(),
+            contents
+        );
+        let e = read_to_end_limited(contents.as_slice(), 3).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{channel, Receiver, TryRecvError},
+        Arc,
+    },
+    time::Duration,
+};
+
+use egui::{Color32, Key, RichText};
+use egui_phosphor::regular;
+use tempfile::TempDir;
+
+use super::supported_formats::LoadedFiles;
+use crate::{util::format_data_size, APP_NAME};
+
+// Downloads that are larger than this are aborted
+const MAX_DOWNLOAD_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// A downloaded file and the temporary directory it is in, the directory is removed when this is dropped
+#[derive(Debug)]
+struct DownloadedFile {
+    dir: TempDir,
+    path: PathBuf,
+}
+
+/// A download running in a background thread, the file is sent when it has been downloaded
+#[derive(Debug)]
+struct Download {
+    url: String,
+    received_bytes: Arc<AtomicU64>,
+    total_bytes: Arc<AtomicU64>,
+    result: Receiver<io::Result<DownloadedFile>>,
+}
+
+/// Downloads files from `http(s)://` URLs to a temporary directory and loads them like files opened from disk.
+///
+/// The download runs in a background thread so the UI stays responsive for large files.
+#[derive(Debug, Default)]
+pub(crate) struct UrlDownloader {
+    show: bool,
+    url_input: String,
+    err_msg: Option<String>,
+    download: Option<Download>,
+    // The directories of the loaded files, kept until the app exits so the logs can be reloaded from them
+    loaded_dirs: Vec<TempDir>,
+}
+
+impl UrlDownloader {
+    /// Loads the downloaded file into `loaded_files` when a download has finished
+    pub(crate) fn poll_downloaded_file(
+        &mut self,
+        loaded_files: &mut LoadedFiles,
+    ) -> io::Result<()> {
+        let Some(download) = &self.download else {
+            return Ok(());
+        };
+        let downloaded = match download.result.try_recv() {
+            Ok(res) => res,
+            Err(TryRecvError::Empty) => return Ok(()),
+            Err(TryRecvError::Disconnected) => {
+                Err(io::Error::other("The download was interrupted"))
+            }
+        };
+        let url = download.url.clone();
+        self.download = None;
+        // The directory of a file that fails to download or load is removed when it is dropped here
+        let res = downloaded.and_then(|downloaded| {
+            loaded_files.parse_path(&downloaded.path)?;
+            self.loaded_dirs.push(downloaded.dir);
+            Ok(())
+        });
+        if let Err(e) = &res {
+            self.err_msg = Some(format!("Failed loading {url}: {e}"));
+        } else {
+            self.show = false;
+            self.url_input.clear();
+        }
+        res
+    }
+
+    /// Shows a button that opens a window for entering the URL to download
+    pub(crate) fn show(&mut self, ui: &mut egui::Ui) {
+        ui.toggle_value(&mut self.show, format!("{} Open URL", regular::GLOBE))
+            .on_hover_text("Download and load a file from an http(s) URL");
+        if !self.show {
+            return;
+        }
+        let mut open = self.show;
+        egui::Window::new(format!("{} Open URL", regular::GLOBE))
+            .open(&mut open)
+            .show(ui.ctx(), |ui| self.ui(ui));
+        self.show = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        if let Some(download) = &self.download {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(download_progress(
+                    download.received_bytes.load(Ordering::Relaxed),
+                    download.total_bytes.load(Ordering::Relaxed),
+                ));
+            });
+            ui.label(&download.url);
+            // The progress is updated from the download thread
+            ui.ctx().request_repaint_after(Duration::from_millis(100));
+            return;
+        }
+        ui.horizontal(|ui| {
+            let resp = ui.add(
+                egui::TextEdit::singleline(&mut self.url_input)
+                    .hint_text("https://")
+                    .desired_width(400.0),
+            );
+            let entered = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if entered
+                || ui
+                    .button(format!("{} Download", regular::DOWNLOAD_SIMPLE))
+                    .clicked()
+            {
+                self.start_download(ui.ctx().clone());
+            }
+        });
+        if let Some(err_msg) = &self.err_msg {
+            ui.label(RichText::new(format!("⚠ {err_msg}")).color(Color32::YELLOW));
+        }
+    }
+
+    fn start_download(&mut self, ctx: egui::Context) {
+        let url = self.url_input.trim().to_owned();
+        if !is_http_url(&url) {
+            self.err_msg = Some(format!(
+                "'{url}' is not an http(s) URL, other schemes such as s3:// are not supported"
+            ));
+            return;
+        }
+        self.err_msg = None;
+        let received_bytes = Arc::new(AtomicU64::new(0));
+        let total_bytes = Arc::new(AtomicU64::new(0));
+        let (sender, result) = channel();
+        let thread_url = url.clone();
+        let thread_received_bytes = Arc::clone(&received_bytes);
+        let thread_total_bytes = Arc::clone(&total_bytes);
+        let spawned = std::thread::Builder::new()
+            .name("URL download thread".to_owned())
+            .spawn(move || {
+                let res =
+                    download_to_temp_dir(&thread_url, &thread_received_bytes, &thread_total_bytes);
+                let _ = sender.send(res);
+                ctx.request_repaint();
+            });
+        if let Err(e) = spawned {
+            self.err_msg = Some(format!("Failed starting the download: {e}"));
+            return;
+        }
+        self.download = Some(Download {
+            url,
+            received_bytes,
+            total_bytes,
+            result,
+        });
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+fn download_progress(received_bytes: u64, total_bytes: u64) -> String {
+    let received = format_data_size(received_bytes as usize);
+    if total_bytes == 0 {
+        format!("Downloaded {received}")
+    } else {
+        let percent = received_bytes as f64 / total_bytes as f64 * 100.0;
+        format!(
+            "Downloaded {received} of {} ({percent:.0}%)",
+            format_data_size(total_bytes as usize)
+        )
+    }
+}
+
+/// Returns the name of the file at the end of the path of the `url`, e.g. `data.zip` for `https://host/logs/data.zip?token=abc`
+fn file_name_from_url(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = without_scheme
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(without_scheme);
+    path.split_once('/')
+        .and_then(|(_host, path)| path.rsplit('/').find(|segment| !segment.is_empty()))
+        .filter(|name| *name != "." && *name != ".." && !name.contains('\\'))
+        .unwrap_or("download")
+        .to_owned()
+}
+
+fn too_large_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!(
+            "The file is larger than the maximum download size of {}",
+            format_data_size(MAX_DOWNLOAD_BYTES as usize)
+        ),
+    )
+}
+
+/// Fails if the `content_type` of a response is a web page, e.g. a login page of a server that requires authentication,
+/// or a page about a file that was moved, instead of the file
+fn check_content_type(content_type: Option<&str>) -> io::Result<()> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+    let mime_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if mime_type == "text/html" || mime_type == "application/xhtml+xml" {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "The URL returned a web page instead of a file, it might require a login",
+        ));
+    }
+    Ok(())
+}
+
+/// Downloads the `url` to a new temporary directory, the directory is removed again if the download fails.
+///
+/// Redirects are followed. The file keeps the name from the `url` so zip and gzip files are recognized by their extension.
+fn download_to_temp_dir(
+    url: &str,
+    received_bytes: &AtomicU64,
+    total_bytes: &AtomicU64,
+) -> io::Result<DownloadedFile> {
+    let mut response = reqwest::blocking::get(url)
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .map_err(io::Error::other)?;
+    check_content_type(
+        response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok()),
+    )?;
+    if let Some(len) = response.content_length() {
+        if len > MAX_DOWNLOAD_BYTES {
+            return Err(too_large_error());
+        }
+        total_bytes.store(len, Ordering::Relaxed);
+    }
+    // A directory per download so files with the same name don't overwrite each other
+    let dir = tempfile::Builder::new()
+        .prefix(&format!("{APP_NAME}-download-"))
+        .tempdir()?;
+    let path = dir.path().join(file_name_from_url(url));
+    write_with_progress(&mut response, &path, received_bytes, MAX_DOWNLOAD_BYTES)?;
+    Ok(DownloadedFile { dir, path })
+}
+
+/// Writes the content of `reader` to `path`, fails if there's more than `max_bytes`
+fn write_with_progress(
+    reader: &mut impl Read,
+    path: &Path,
+    received_bytes: &AtomicU64,
+    max_bytes: u64,
+) -> io::Result<()> {
+    let mut file = io::BufWriter::new(fs::File::create(path)?);
+    let mut buf = vec![0; 64 * 1024];
+    let mut written: u64 = 0;
+    loop {
+        let read = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        written += read as u64;
+        if written > max_bytes {
+            return Err(too_large_error());
+        }
+        file.write_all(&buf[..read])?;
+        received_bytes.fetch_add(read as u64, Ordering::Relaxed);
+    }
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use testresult::TestResult;
+
+    #[test]
+    fn test_file_name_from_url() {
+        assert_eq!(
+            file_name_from_url("https://logs.example.com/2024/09/data.zip?token=abc#top"),
+            "data.zip"
+        );
+        assert_eq!(
+            file_name_from_url("http://logs.example.com/pid_20240926_121708_00.bin/"),
+            "pid_20240926_121708_00.bin"
+        );
+        assert_eq!(file_name_from_url("https://logs.example.com"), "download");
+        assert_eq!(
+            file_name_from_url("https://logs.example.com/a/.."),
+            "download"
+        );
+    }
+
+    #[test]
+    fn test_is_http_url() {
+        assert!(is_http_url("https://logs.example.com/a.bin"));
+        assert!(is_http_url("http://logs.example.com/a.bin"));
+        assert!(!is_http_url("s3://bucket/a.bin"));
+        assert!(!is_http_url("/home/user/a.bin"));
+    }
+
+    #[test]
+    fn test_check_content_type() {
+        assert!(check_content_type(None).is_ok());
+        assert!(check_content_type(Some("application/octet-stream")).is_ok());
+        assert!(check_content_type(Some("application/zip")).is_ok());
+        assert!(check_content_type(Some("text/plain; charset=utf-8")).is_ok());
+        assert!(check_content_type(Some("text/html")).is_err());
+        assert!(check_content_type(Some("Text/HTML; charset=UTF-8")).is_err());
+    }
+
+    #[test]
+    fn test_write_with_progress() -> TestResult {
+        let tmp_dir = tempdir()?;
+        let path = tmp_dir.path().join("a.bin");
+        let contents = vec![7; 200_000];
+        let received_bytes = AtomicU64::new(0);
+        write_with_progress(&mut contents.as_slice(), &path, &received_bytes, 200_000)?;
+        assert_eq!(fs::read(&path)?, contents);
+        assert_eq!(received_bytes.load(Ordering::Relaxed), 200_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_with_progress_too_large() -> TestResult {
+        let tmp_dir = tempdir()?;
+        let path = tmp_dir.path().join("a.bin");
+        let contents = vec![7; 200_000];
+        let received_bytes = AtomicU64::new(0);
+        let Err(e) = write_with_progress(&mut contents.as_slice(), &path, &received_bytes, 199_999)
+        else {
+            panic!("Expected the download to be too large");
+        };
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::sync::OnceLock;
 
-pub use app::App;
+pub use app::{supported_formats::RegisteredFormat, App};
 use semver::Version;
 mod app;
 
@@ -1,37 +1,73 @@
 use std::time::Duration;
 
 use click_delta::ClickDelta;
+#[cfg(not(target_arch = "wasm32"))]
+use csv_export::CsvExport;
 use egui_notify::Toasts;
+use gap_finder::GapFinder;
+use legend_settings::LegendSettings;
+use log_if::prelude::{ExpectedPlotRange, Plotable};
 use plot_settings::PlotSettings;
 use plot_util::Plots;
+use plot_window::PlotWindow;
+use sample_cursor::SampleCursor;
 use serde::{Deserialize, Serialize};
+use spectrum::Spectrum;
+use xy_plot::XyPlot;
 
 use axis_config::AxisConfig;
 use egui::{Id, Response};
-use egui_plot::Legend;
 
-use crate::app::supported_formats::SupportedFormat;
+use crate::{
+    app::supported_formats::{LoadedFile, RegisteredFormat, SupportedFormat},
+    util::{format_data_size, format_large_number},
+};
+pub(crate) use axis_config::DisplayTimeZone;
+pub(crate) use number_format::NumberFormat;
 mod axis_config;
 mod click_delta;
+#[cfg(not(target_arch = "wasm32"))]
+mod csv_export;
+mod gap_finder;
+mod legend_settings;
+mod number_format;
 mod plot_graphics;
 mod plot_settings;
 mod plot_ui;
+mod plot_window;
+mod sample_cursor;
+mod spectrum;
 mod util;
+mod xy_plot;
 
-#[derive(Debug, strum_macros::Display, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(
+    Debug, strum_macros::Display, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize,
+)]
 pub enum PlotType {
     Percentage,
     Hundreds,
     Thousands,
 }
 
+impl From<ExpectedPlotRange> for PlotType {
+    fn from(value: ExpectedPlotRange) -> Self {
+        match value {
+            ExpectedPlotRange::Percentage => Self::Percentage,
+            ExpectedPlotRange::OneToOneHundred => Self::Hundreds,
+            ExpectedPlotRange::Thousands => Self::Thousands,
+        }
+    }
+}
+
 #[allow(
     missing_debug_implementations,
     reason = "Legend is from egui_plot and doesn't implement debug"
 )]
 #[derive(PartialEq, Deserialize, Serialize)]
+#[serde(default)] // the nested settings have defaults as well, so the state of older versions can be restored
 pub struct LogPlotUi {
-    legend_cfg: Legend,
+    legend_cfg: LegendSettings,
+    number_format: NumberFormat,
     line_width: f32,
     axis_config: AxisConfig,
     plots: Plots,
@@ -39,12 +75,25 @@ pub struct LogPlotUi {
     x_min_max: Option<(f64, f64)>,
     link_group: Option<Id>,
     click_delta: ClickDelta,
+    sample_cursor: SampleCursor,
+    #[serde(skip)]
+    plot_windows: Vec<PlotWindow>,
+    #[serde(skip)]
+    xy_plot: XyPlot,
+    #[serde(skip)]
+    gap_finder: GapFinder,
+    #[serde(skip)]
+    spectrum: Spectrum,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    csv_export: CsvExport,
 }
 
 impl Default for LogPlotUi {
     fn default() -> Self {
         Self {
             legend_cfg: Default::default(),
+            number_format: NumberFormat::default(),
             line_width: 1.5,
             axis_config: Default::default(),
             plots: Plots::default(),
@@ -52,6 +101,13 @@ impl Default for LogPlotUi {
             x_min_max: None,
             link_group: None,
             click_delta: ClickDelta::default(),
+            sample_cursor: SampleCursor::default(),
+            plot_windows: Vec::new(),
+            xy_plot: XyPlot::default(),
+            gap_finder: GapFinder::default(),
+            spectrum: Spectrum::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            csv_export: CsvExport::default(),
         }
     }
 }
@@ -63,14 +119,40 @@ impl LogPlotUi {
             + self.plots.thousands().plots().len()
     }
 
+    /// Shows the number of loaded data points and approximately how much memory they use, if any plots are loaded.
+    pub fn show_data_usage(&self, ui: &mut egui::Ui) {
+        if self.plot_count() == 0 {
+            return;
+        }
+        ui.label(format!(
+            "{points} points (~{size})",
+            points = format_large_number(self.plots.total_data_points(), self.number_format),
+            size = format_data_size(self.plots.estimated_memory_bytes()),
+        ))
+        .on_hover_text("Loaded data points and the approximate memory used by them, including downsampled copies of the points");
+    }
+
+    /// Adds the plots (and labels) of `data` as if it was loaded from a file.
+    pub fn add_plotable(&mut self, data: &dyn Plotable) {
+        util::add_plot_data_to_plot_collections(
+            &mut self.plots,
+            data,
+            None,
+            None,
+            &mut self.plot_settings,
+        );
+    }
+
     pub fn ui(
         &mut self,
         ui: &mut egui::Ui,
-        loaded_files: &[SupportedFormat],
+        loaded_files: &[LoadedFile],
+        formats: &[RegisteredFormat],
         toasts: &mut Toasts,
     ) -> Response {
         let Self {
             legend_cfg,
+            number_format,
             line_width,
             axis_config,
             plots,
@@ -78,6 +160,13 @@ impl LogPlotUi {
             x_min_max,
             link_group,
             click_delta,
+            sample_cursor,
+            plot_windows,
+            xy_plot,
+            gap_finder,
+            spectrum,
+            #[cfg(not(target_arch = "wasm32"))]
+            csv_export,
         } = self;
 
         if link_group.is_none() {
@@ -91,35 +180,126 @@ impl LogPlotUi {
 
         plots.calc_all_plot_x_min_max(x_min_max);
 
-        plot_ui::show_settings_grid(ui, line_width, axis_config, plot_settings);
+        plot_ui::show_settings_grid(
+            ui,
+            line_width,
+            axis_config,
+            plots,
+            plot_settings,
+            click_delta,
+            plot_windows,
+            xy_plot,
+            gap_finder,
+            spectrum,
+            #[cfg(not(target_arch = "wasm32"))]
+            csv_export,
+            legend_cfg,
+            number_format,
+        );
 
-        for log in loaded_files {
-            util::add_plot_data_to_plot_collections(plots, log, plot_settings);
-        }
-        if !loaded_files.is_empty() {
-            log::info!("Total data points: {}", plots.total_data_points());
-            toasts
-                .info(format!(
-                    "Total data points in loaded files: {}",
-                    plots.total_data_points(),
-                ))
-                .duration(Some(Duration::from_secs(20)));
+        add_loaded_files(plots, plot_settings, loaded_files, *number_format, toasts);
+        reload_requested_logs(plots, plot_settings, formats, toasts);
+
+        plot_settings.refresh(plots, toasts);
+
+        let response = ui
+            .vertical(|ui| {
+                plot_graphics::paint_plots(
+                    ui,
+                    plots,
+                    plot_settings,
+                    legend_cfg,
+                    axis_config,
+                    link_group.expect("uninitialized link group id"),
+                    *line_width,
+                    click_delta,
+                    sample_cursor,
+                    None,
+                );
+            })
+            .response;
+
+        let plot_names = plot_settings.plot_names();
+        for plot_window in plot_windows.iter_mut() {
+            plot_window.show(ui.ctx(), &plot_names, |ui, series_subset| {
+                plot_graphics::paint_plots(
+                    ui,
+                    plots,
+                    plot_settings,
+                    legend_cfg,
+                    axis_config,
+                    link_group.expect("uninitialized link group id"),
+                    *line_width,
+                    click_delta,
+                    sample_cursor,
+                    Some(series_subset),
+                );
+            });
         }
+        plot_windows.retain(PlotWindow::is_open);
+        // All plots have been painted
+        axis_config.clear_reset_zoom_request();
+
+        xy_plot.show(ui.ctx(), plots, plot_settings);
+        gap_finder.show(ui.ctx(), plots, plot_settings, axis_config);
+        let visible_x_range = axis_config.visible_x_range();
+        spectrum.show(ui.ctx(), plots, plot_settings, visible_x_range);
+        #[cfg(not(target_arch = "wasm32"))]
+        csv_export.show(ui.ctx(), plots, plot_settings, visible_x_range);
+
+        response
+    }
+}
 
-        plot_settings.refresh(plots);
-
-        ui.vertical(|ui| {
-            plot_graphics::paint_plots(
-                ui,
-                plots,
-                plot_settings,
-                legend_cfg,
-                axis_config,
-                link_group.expect("uninitialized link group id"),
-                *line_width,
-                click_delta,
-            );
-        })
-        .response
+fn add_loaded_files(
+    plots: &mut Plots,
+    plot_settings: &mut PlotSettings,
+    loaded_files: &[LoadedFile],
+    number_format: NumberFormat,
+    toasts: &mut Toasts,
+) {
+    for loaded_file in loaded_files {
+        let log = loaded_file.format();
+        util::add_plot_data_to_plot_collections(
+            plots,
+            log,
+            log.parse_info(),
+            loaded_file.source_path().map(ToOwned::to_owned),
+            plot_settings,
+        );
+    }
+    if !loaded_files.is_empty() {
+        log::info!("Total data points: {}", plots.total_data_points());
+        toasts
+            .info(format!(
+                "Total data points in loaded files: {}",
+                format_large_number(plots.total_data_points(), number_format),
+            ))
+            .duration(Some(Duration::from_secs(20)));
+    }
+}
+
+/// Parses the logs the user requested to be reloaded again from their source path and replaces their plots.
+fn reload_requested_logs(
+    plots: &mut Plots,
+    plot_settings: &mut PlotSettings,
+    formats: &[RegisteredFormat],
+    toasts: &mut Toasts,
+) {
+    for (log_id, path) in plot_settings.take_reload_requests() {
+        match SupportedFormat::reload_from_path(formats, &path) {
+            Ok(log) => {
+                util::reload_plot_data(plots, log_id, &log, log.parse_info(), plot_settings);
+                toasts
+                    .info(format!("Reloaded {}", log.descriptive_name()))
+                    .duration(Some(Duration::from_secs(2)));
+            }
+            Err(e) => {
+                log::error!("Failed reloading '{}': {e}", path.display());
+                toasts
+                    .error(format!("Failed reloading log #{log_id}: {e}"))
+                    .duration(Some(Duration::from_secs(10)));
+            }
+        }
     }
 }
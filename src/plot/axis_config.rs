@@ -1,30 +1,99 @@
 use axis_lock::YAxisLock;
 use egui::{Color32, RichText};
 use egui_phosphor::regular;
+use goto_time::GotoTime;
+use log_axis::LogYAxis;
+pub use log_axis::{format_log10_tick, format_log10_value};
+use secondary_axis::SecondaryYAxis;
+pub use time_zone::DisplayTimeZone;
 
 use super::PlotType;
 
 mod axis_lock;
+mod goto_time;
+mod log_axis;
+mod secondary_axis;
+mod time_zone;
+
+/// How the axes of the plot areas are linked when panning and zooming
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub enum LinkMode {
+    /// Pan and zoom time together but keep the y-scale of each plot area independent
+    #[default]
+    #[strum(to_string = "X-axis only")]
+    XOnly,
+    #[strum(to_string = "Both axes")]
+    Both,
+    Unlinked,
+}
+
+impl LinkMode {
+    /// Returns whether the x- and y-axis are linked respectively
+    pub fn linked_axes(self) -> egui::Vec2b {
+        match self {
+            Self::XOnly => egui::Vec2b::new(true, false),
+            Self::Both => egui::Vec2b::TRUE,
+            Self::Unlinked => egui::Vec2b::FALSE,
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
 pub struct AxisConfig {
-    link_x: bool,
+    link_mode: LinkMode,
     link_cursor_x: bool,
     show_axes: bool,
     show_grid: bool,
     y_axis_lock: YAxisLock,
+    secondary_y_axis: SecondaryYAxis,
+    log_y_axis: LogYAxis,
+    time_zone: DisplayTimeZone,
+    goto_time: GotoTime,
     pub ui_visible: bool,
+    #[serde(skip)]
+    reset_zoom_requested: bool,
+    #[serde(skip)]
+    confirm_reset_zoom_visible: bool,
+    // Time range (in ns) to zoom all plots to
+    #[serde(skip)]
+    zoom_x_requested: Option<(f64, f64)>,
+    // Time (in ns) to center all plots on, keeping their zoom level
+    #[serde(skip)]
+    center_x_requested: Option<f64>,
+    // Time range (in ns) shown in the plots when they were last painted
+    #[serde(skip)]
+    visible_x_range: Option<(f64, f64)>,
 }
 
 impl Default for AxisConfig {
     fn default() -> Self {
         Self {
-            link_x: true,
+            link_mode: LinkMode::default(),
             link_cursor_x: true,
             show_axes: true,
             show_grid: false,
             y_axis_lock: YAxisLock::default(),
+            secondary_y_axis: SecondaryYAxis::default(),
+            log_y_axis: LogYAxis::default(),
+            time_zone: DisplayTimeZone::default(),
+            goto_time: GotoTime::default(),
             ui_visible: false,
+            reset_zoom_requested: false,
+            confirm_reset_zoom_visible: false,
+            zoom_x_requested: None,
+            center_x_requested: None,
+            visible_x_range: None,
         }
     }
 }
@@ -34,8 +103,8 @@ impl AxisConfig {
         self.show_axes
     }
 
-    pub fn link_x(&self) -> bool {
-        self.link_x
+    pub fn link_mode(&self) -> LinkMode {
+        self.link_mode
     }
 
     pub fn link_cursor_x(&self) -> bool {
@@ -46,6 +115,114 @@ impl AxisConfig {
         self.show_grid
     }
 
+    pub fn secondary_y_axis(&self) -> &SecondaryYAxis {
+        &self.secondary_y_axis
+    }
+
+    pub fn log_y_axis(&self) -> &LogYAxis {
+        &self.log_y_axis
+    }
+
+    pub fn time_zone(&self) -> DisplayTimeZone {
+        self.time_zone
+    }
+
+    /// Returns true if the zoom of all plots should be reset this frame.
+    pub fn reset_zoom_requested(&self) -> bool {
+        self.reset_zoom_requested
+    }
+
+    /// Requests zooming all plots to the time range from `x_min` to `x_max` on the next frame.
+    pub fn request_zoom_x(&mut self, x_min: f64, x_max: f64) {
+        self.zoom_x_requested = Some((x_min, x_max));
+    }
+
+    /// Returns the time range all plots should be zoomed to this frame, if any.
+    pub fn zoom_x_requested(&self) -> Option<(f64, f64)> {
+        self.zoom_x_requested
+    }
+
+    /// Returns the time all plots should be centered on this frame, if any.
+    pub fn center_x_requested(&self) -> Option<f64> {
+        self.center_x_requested
+    }
+
+    /// The time range (in ns) shown in the plots, if they have been painted.
+    pub fn visible_x_range(&self) -> Option<(f64, f64)> {
+        self.visible_x_range
+    }
+
+    pub fn set_visible_x_range(&mut self, x_min: f64, x_max: f64) {
+        self.visible_x_range = Some((x_min, x_max));
+    }
+
+    /// The time marked by the go to time input, if any.
+    pub fn time_marker(&self) -> Option<f64> {
+        self.goto_time.marker()
+    }
+
+    /// Clears the requests to reset or change the zoom, should be called once all plots have been painted.
+    pub fn clear_reset_zoom_request(&mut self) {
+        self.reset_zoom_requested = false;
+        self.zoom_x_requested = None;
+        self.center_x_requested = None;
+    }
+
+    /// Shows the input for a time to center all plots on.
+    pub fn goto_time_ui(&mut self, ui: &mut egui::Ui) {
+        if let Some(time_ns) = self.goto_time.ui(ui) {
+            self.center_x_requested = Some(time_ns);
+        }
+    }
+
+    /// Shows the button for resetting the zoom of all plots.
+    ///
+    /// `bounds_info_ui` is added to the hover text of the button, it is only called while the button is hovered.
+    /// If the Y-axis is locked, the user is asked whether the lock should be cleared as well.
+    pub fn reset_zoom_ui(&mut self, ui: &mut egui::Ui, bounds_info_ui: impl FnOnce(&mut egui::Ui)) {
+        if ui
+            .button(format!("{} Reset zoom", regular::ARROWS_OUT))
+            .on_hover_ui(|ui| {
+                ui.label("Fit all plots to their data");
+                bounds_info_ui(ui);
+            })
+            .clicked()
+        {
+            if self.y_axis_lock.lock_y_axis {
+                self.confirm_reset_zoom_visible = true;
+            } else {
+                self.reset_zoom_requested = true;
+            }
+        }
+        if self.confirm_reset_zoom_visible {
+            egui::Window::new("Reset zoom")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                .show(ui.ctx(), |ui| {
+                    ui.label("The Y-axis is locked. Do you want to unlock it?");
+                    ui.horizontal(|ui| {
+                        if ui.button("Unlock and reset").clicked() {
+                            self.y_axis_lock.lock_y_axis = false;
+                            self.reset_zoom_requested = true;
+                            self.confirm_reset_zoom_visible = false;
+                        }
+                        if ui
+                            .button("Keep lock")
+                            .on_hover_text("Only the X-axis is reset")
+                            .clicked()
+                        {
+                            self.reset_zoom_requested = true;
+                            self.confirm_reset_zoom_visible = false;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_reset_zoom_visible = false;
+                        }
+                    });
+                });
+        }
+    }
+
     pub fn handle_y_axis_lock<F>(
         &mut self,
         plot_ui: &mut egui_plot::PlotUi,
@@ -58,16 +235,22 @@ impl AxisConfig {
             .handle(plot_ui, plot_type, between_bounds_update_fn);
     }
 
-    pub fn toggle_axis_cfg_ui(&mut self, ui: &mut egui::Ui) {
-        let linked_x_axis_text = format!(
+    pub fn toggle_axis_cfg_ui(&mut self, ui: &mut egui::Ui, plot_names: &[&str]) {
+        let linked_axes_text = format!(
             "{} Linked Axes",
-            if self.link_x {
-                regular::LINK_SIMPLE
-            } else {
+            if self.link_mode == LinkMode::Unlinked {
                 regular::LINK_BREAK
+            } else {
+                regular::LINK_SIMPLE
             }
         );
-        ui.toggle_value(&mut self.link_x, linked_x_axis_text);
+        egui::ComboBox::from_label(linked_axes_text)
+            .selected_text(self.link_mode.to_string())
+            .show_ui(ui, |ui| {
+                for mode in [LinkMode::XOnly, LinkMode::Both, LinkMode::Unlinked] {
+                    ui.selectable_value(&mut self.link_mode, mode, mode.to_string());
+                }
+            });
         let linked_x_cursor_text = format!(
             "{} Linked Cursors",
             if self.link_cursor_x {
@@ -111,5 +294,11 @@ impl AxisConfig {
         };
 
         ui.toggle_value(&mut self.y_axis_lock.lock_y_axis, lock_y_axis_text);
+        ui.separator();
+        self.time_zone.show(ui);
+        ui.separator();
+        self.log_y_axis.show(ui);
+        ui.separator();
+        self.secondary_y_axis.show(ui, plot_names);
     }
 }
@@ -0,0 +1,105 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use egui::{Color32, Key, RichText};
+use egui_phosphor::regular;
+use serde::{Deserialize, Serialize};
+
+// Formats of timestamps without a time zone, they are interpreted as UTC
+const NAIVE_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
+
+/// Input for a timestamp to center all plots on, optionally marking it with a vertical line.
+#[derive(Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct GotoTime {
+    drop_marker: bool,
+    #[serde(skip)]
+    input: String,
+    #[serde(skip)]
+    err_msg: Option<String>,
+    // Time (in ns) of the marker
+    #[serde(skip)]
+    marker: Option<f64>,
+}
+
+impl GotoTime {
+    /// The time (in ns) to mark with a vertical line, if any
+    pub fn marker(&self) -> Option<f64> {
+        self.marker
+    }
+
+    /// Shows the input, returns the time (in ns) to center the plots on when a valid time is entered
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> Option<f64> {
+        let mut goto = None;
+        ui.horizontal(|ui| {
+            let resp = ui
+                .add(
+                    egui::TextEdit::singleline(&mut self.input)
+                        .hint_text("Go to time")
+                        .desired_width(160.0),
+                )
+                .on_hover_text("ISO 8601 (UTC unless an offset is given) e.g. 2024-09-26 12:17:08 or unix time in seconds\nPress enter to center the plots on the time");
+            let entered = resp.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if entered || ui.button(regular::CROSSHAIR).on_hover_text("Go to time").clicked() {
+                match parse_time_ns(&self.input) {
+                    Ok(time_ns) => {
+                        self.err_msg = None;
+                        self.marker = self.drop_marker.then_some(time_ns);
+                        goto = Some(time_ns);
+                    }
+                    Err(e) => self.err_msg = Some(e),
+                }
+            }
+            ui.checkbox(&mut self.drop_marker, "Marker")
+                .on_hover_text("Mark the time with a vertical line");
+            if !self.drop_marker {
+                self.marker = None;
+            }
+            if let Some(err_msg) = &self.err_msg {
+                ui.label(RichText::new(format!("⚠ {err_msg}")).color(Color32::YELLOW));
+            }
+        });
+        goto
+    }
+}
+
+/// Parses a timestamp as RFC 3339, ISO 8601 without a time zone (as UTC) or unix time in seconds and returns it in nanoseconds
+fn parse_time_ns(input: &str) -> Result<f64, String> {
+    let input = input.trim();
+    if let Ok(unix_s) = input.parse::<f64>() {
+        return Ok(unix_s * 1_000_000_000.0);
+    }
+    let utc = if let Ok(dt) = DateTime::parse_from_rfc3339(input) {
+        dt.to_utc()
+    } else if let Some(dt) = NAIVE_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(input, fmt).ok())
+    {
+        dt.and_utc()
+    } else {
+        return Err(format!("'{input}' is not a recognized time"));
+    };
+    to_ns(utc)
+}
+
+fn to_ns(utc: DateTime<Utc>) -> Result<f64, String> {
+    utc.timestamp_nanos_opt()
+        .map(|ns| ns as f64)
+        .ok_or_else(|| format!("{utc} is out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_ns() {
+        let expected = 1_727_353_028_000_000_000.0;
+        assert_eq!(parse_time_ns("1727353028"), Ok(expected));
+        assert_eq!(parse_time_ns("2024-09-26T12:17:08Z"), Ok(expected));
+        assert_eq!(parse_time_ns("2024-09-26T14:17:08+02:00"), Ok(expected));
+        assert_eq!(parse_time_ns(" 2024-09-26 12:17:08 "), Ok(expected));
+        assert_eq!(
+            parse_time_ns("2024-09-26T12:17:08.5"),
+            Ok(expected + 500_000_000.0)
+        );
+        assert!(parse_time_ns("yesterday").is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use egui::RichText;
+use egui_plot::GridMark;
+use plot_util::NonPositiveValues;
+use serde::{Deserialize, Serialize};
+
+use super::PlotType;
+
+/// Keeps track of which plot areas have a logarithmic (base 10) y-axis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LogYAxis {
+    percentage: bool,
+    hundreds: bool,
+    thousands: bool,
+    non_positive: NonPositiveValues,
+}
+
+impl LogYAxis {
+    /// Returns how non-positive values should be handled if the plot area is on a logarithmic scale
+    /// or [`None`] if it is on a linear scale.
+    pub fn scale(&self, plot_type: PlotType) -> Option<NonPositiveValues> {
+        let is_log = match plot_type {
+            PlotType::Percentage => self.percentage,
+            PlotType::Hundreds => self.hundreds,
+            PlotType::Thousands => self.thousands,
+        };
+        is_log.then_some(self.non_positive)
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new("Logarithmic Y-axis").strong())
+            .on_hover_text("Plots the base 10 logarithm of the values in the selected plot areas");
+        ui.horizontal_wrapped(|ui| {
+            ui.toggle_value(&mut self.percentage, "Percentage");
+            ui.toggle_value(&mut self.hundreds, "To hundreds");
+            ui.toggle_value(&mut self.thousands, "Thousands");
+        });
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Values ≤ 0:");
+            ui.radio_value(&mut self.non_positive, NonPositiveValues::Skip, "Skip")
+                .on_hover_text("Leave out values that are zero or negative");
+            ui.radio_value(&mut self.non_positive, NonPositiveValues::Clamp, "Clamp")
+                .on_hover_text(format!(
+                    "Clamp values that are zero or negative to {:e}",
+                    plot_util::LOG10_CLAMP_EPSILON
+                ));
+        });
+    }
+}
+
+/// Formats a tick on a logarithmic y-axis where the `mark` value is the exponent of the value to show.
+///
+/// Decades are shown without a mantissa, e.g. `100` or `1e-6`.
+pub fn format_log10_tick(mark: GridMark, _range: &std::ops::RangeInclusive<f64>) -> String {
+    format_log10_value(mark.value)
+}
+
+/// Formats the value `10^exponent`, see [`format_log10_tick`].
+pub fn format_log10_value(exponent: f64) -> String {
+    const PLAIN_EXPONENTS: std::ops::RangeInclusive<f64> = -3.0..=3.0;
+    let value = 10_f64.powf(exponent);
+    let is_decade = (exponent - exponent.round()).abs() < 1e-9;
+    if is_decade {
+        let exponent = exponent.round();
+        if PLAIN_EXPONENTS.contains(&exponent) {
+            let decimals = (-exponent).max(0.0) as usize;
+            format!("{value:.decimals$}")
+        } else {
+            format!("1e{exponent}")
+        }
+    } else if PLAIN_EXPONENTS.contains(&exponent) {
+        // Two significant digits
+        let decimals = (1.0 - exponent.floor()).max(0.0) as usize;
+        format!("{value:.decimals$}")
+    } else {
+        format!("{value:.1e}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_decade_boundaries() {
+        assert_eq!(format_log10_value(-4.0), "1e-4");
+        assert_eq!(format_log10_value(-3.0), "0.001");
+        assert_eq!(format_log10_value(-1.0), "0.1");
+        assert_eq!(format_log10_value(0.0), "1");
+        assert_eq!(format_log10_value(1.0), "10");
+        assert_eq!(format_log10_value(3.0), "1000");
+        assert_eq!(format_log10_value(4.0), "1e4");
+        assert_eq!(format_log10_value(-9.0), "1e-9");
+    }
+
+    #[test]
+    fn test_format_between_decades() {
+        assert_eq!(format_log10_value(0.5), "3.2");
+        assert_eq!(format_log10_value(2.5), "316");
+        assert_eq!(format_log10_value(-1.5), "0.032");
+        assert_eq!(format_log10_value(5.5), "3.2e5");
+    }
+
+    #[test]
+    fn test_scale_per_plot_type() {
+        let log_axis = LogYAxis {
+            thousands: true,
+            ..Default::default()
+        };
+        assert_eq!(log_axis.scale(PlotType::Percentage), None);
+        assert_eq!(
+            log_axis.scale(PlotType::Thousands),
+            Some(NonPositiveValues::Skip)
+        );
+    }
+}
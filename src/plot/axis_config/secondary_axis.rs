@@ -0,0 +1,161 @@
+use egui::RichText;
+use plot_util::{NonPositiveValues, PlotValues, YTransform};
+use serde::{Deserialize, Serialize};
+
+/// Keeps track of which plots (by name) are bound to the secondary (left) y-axis.
+///
+/// Plots on the secondary axis are scaled at draw time such that their range fills the range
+/// of the plots on the primary axis within the same plot area.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SecondaryYAxis {
+    plot_names: Vec<String>,
+}
+
+impl SecondaryYAxis {
+    pub fn contains(&self, plot_name: &str) -> bool {
+        self.plot_names.iter().any(|n| n == plot_name)
+    }
+
+    /// Returns whether the plot with the given label is bound to the secondary axis.
+    ///
+    /// The label is on the form `"<name> #<log_id>"` optionally followed by ` (min)` or ` (max)` if the plot is downsampled
+    pub fn contains_label(&self, plot_label: &str) -> bool {
+        let label = plot_label
+            .strip_suffix(" (min)")
+            .or_else(|| plot_label.strip_suffix(" (max)"))
+            .unwrap_or(plot_label);
+        label
+            .rsplit_once(" #")
+            .is_some_and(|(name, _log_id)| self.contains(name))
+    }
+
+    fn toggle(&mut self, plot_name: &str) {
+        if let Some(idx) = self.plot_names.iter().position(|n| n == plot_name) {
+            self.plot_names.remove(idx);
+        } else {
+            self.plot_names.push(plot_name.to_owned());
+        }
+    }
+
+    /// Calculates the transform that maps the plots on the secondary axis onto the range of the plots on the primary axis.
+    ///
+    /// `unit_conversion` returns the unit conversion of a plot as `(scale, offset)` which is applied before the ranges are calculated.
+    /// If `log10` is set both axes are on a logarithmic scale so the ranges are matched in log space.
+    /// Non-positive values are always clamped when calculating the ranges.
+    ///
+    /// Returns [`None`] if none of the `plots` are bound to the secondary axis.
+    pub fn transform<'pv>(
+        &self,
+        plots: impl Iterator<Item = &'pv PlotValues>,
+        unit_conversion: impl Fn(&PlotValues) -> Option<(f64, f64)>,
+        log10: Option<NonPositiveValues>,
+    ) -> Option<YTransform> {
+        let mut primary_range: Option<(f64, f64)> = None;
+        let mut secondary_range: Option<(f64, f64)> = None;
+        for pv in plots {
+            let Some(mut y_min_max) = pv.y_min_max() else {
+                continue;
+            };
+            if let Some((scale, offset)) = unit_conversion(pv) {
+                let (min, max) = (y_min_max.0 * scale + offset, y_min_max.1 * scale + offset);
+                y_min_max = (min.min(max), min.max(max));
+            }
+            if log10.is_some() {
+                let log10 = |y| plot_util::log10_with(y, NonPositiveValues::Clamp);
+                y_min_max = (log10(y_min_max.0), log10(y_min_max.1));
+            }
+            let range = if self.contains(pv.name()) {
+                &mut secondary_range
+            } else {
+                &mut primary_range
+            };
+            *range = Some(range.map_or(y_min_max, |(min, max)| {
+                (min.min(y_min_max.0), max.max(y_min_max.1))
+            }));
+        }
+        let secondary_range = secondary_range?;
+        // If everything is on the secondary axis, we have nothing to scale it to
+        let primary_range = primary_range.unwrap_or(secondary_range);
+        let transform = YTransform::from_ranges(secondary_range, primary_range);
+        Some(match log10 {
+            Some(non_positive) => transform.with_log10(non_positive),
+            None => transform,
+        })
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, plot_names: &[&str]) {
+        ui.label(RichText::new("Secondary (left) Y-axis").strong())
+            .on_hover_text("Plots on the secondary axis are scaled to fill the range of the other plots in the same plot area");
+        if plot_names.is_empty() {
+            ui.label("No plots loaded");
+        }
+        ui.horizontal_wrapped(|ui| {
+            for name in plot_names {
+                let mut on_secondary = self.contains(name);
+                if ui.toggle_value(&mut on_secondary, *name).clicked() {
+                    self.toggle(name);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plot(name: &str, ys: &[f64]) -> PlotValues {
+        let points = ys.iter().enumerate().map(|(i, y)| [i as f64, *y]).collect();
+        PlotValues::new(points, name.to_owned(), 1)
+    }
+
+    #[test]
+    fn test_no_secondary_no_transform() {
+        let secondary = SecondaryYAxis::default();
+        let plots = [plot("Duty cycle", &[0.0, 1.0])];
+        assert_eq!(secondary.transform(plots.iter(), |_| None, None), None);
+    }
+
+    #[test]
+    fn test_secondary_scaled_to_primary() {
+        let mut secondary = SecondaryYAxis::default();
+        secondary.toggle("RPM");
+        let plots = [plot("Duty cycle", &[0.0, 1.0]), plot("RPM", &[0.0, 5000.0])];
+        let t = secondary.transform(plots.iter(), |_| None, None).unwrap();
+        assert_eq!(t.apply(5000.0), 1.0);
+        assert_eq!(t.inverse(0.5), 2500.0);
+    }
+
+    #[test]
+    fn test_secondary_scaled_to_primary_log10() {
+        let mut secondary = SecondaryYAxis::default();
+        secondary.toggle("Leakage");
+        let plots = [
+            plot("Current", &[1.0, 100.0]),
+            plot("Leakage", &[1e-6, 1e-3]),
+        ];
+        let t = secondary
+            .transform(plots.iter(), |_| None, Some(NonPositiveValues::Skip))
+            .unwrap();
+        assert_eq!(t.apply(1e-6), 0.0);
+        assert_eq!(t.apply(1e-3), 2.0);
+    }
+
+    #[test]
+    fn test_contains_label() {
+        let mut secondary = SecondaryYAxis::default();
+        secondary.toggle("RPM");
+        assert!(secondary.contains_label("RPM #1"));
+        assert!(secondary.contains_label("RPM #12 (max)"));
+        assert!(!secondary.contains_label("Duty cycle #1 (min)"));
+    }
+
+    #[test]
+    fn test_toggle_removes() {
+        let mut secondary = SecondaryYAxis::default();
+        secondary.toggle("RPM");
+        assert!(secondary.contains("RPM"));
+        secondary.toggle("RPM");
+        assert!(!secondary.contains("RPM"));
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+use egui::RichText;
+use serde::{Deserialize, Serialize};
+
+/// The time zone that timestamps are displayed in. The timestamps of the plots are always stored as UTC.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DisplayTimeZone {
+    #[default]
+    Utc,
+    /// The time zone of the system the app is running on
+    Local,
+    Fixed {
+        offset_minutes: i32,
+    },
+}
+
+impl DisplayTimeZone {
+    // Covers all time zones in use (UTC-12:00 to UTC+14:00)
+    const OFFSET_MINUTES_RANGE: std::ops::RangeInclusive<i32> = -12 * 60..=14 * 60;
+
+    /// Returns the UTC offset of the time zone at the given time
+    pub fn offset_at(&self, utc: &DateTime<Utc>) -> FixedOffset {
+        match self {
+            Self::Utc => Utc.fix(),
+            Self::Local => utc.with_timezone(&Local).offset().fix(),
+            // Out of range offsets can only come from a corrupt persisted state, fall back to UTC
+            Self::Fixed { offset_minutes } => {
+                FixedOffset::east_opt(offset_minutes * 60).unwrap_or_else(|| Utc.fix())
+            }
+        }
+    }
+
+    /// Converts a UTC timestamp to the time zone
+    pub fn convert(&self, utc: DateTime<Utc>) -> DateTime<FixedOffset> {
+        utc.with_timezone(&self.offset_at(&utc))
+    }
+
+    /// Short description of the time zone e.g. `UTC` or `UTC+02:00`
+    pub fn name(&self) -> String {
+        match self {
+            Self::Utc => "UTC".to_owned(),
+            Self::Local => format!("Local, {}", format_utc_offset(self.offset_at(&Utc::now()))),
+            Self::Fixed { .. } => format_utc_offset(self.offset_at(&Utc::now())),
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new("Time zone").strong())
+            .on_hover_text("The time zone timestamps are displayed in");
+        ui.horizontal_wrapped(|ui| {
+            ui.radio_value(self, Self::Utc, "UTC");
+            ui.radio_value(self, Self::Local, "Local");
+            let is_fixed = matches!(self, Self::Fixed { .. });
+            if ui.radio(is_fixed, "Fixed offset").clicked() && !is_fixed {
+                *self = Self::Fixed { offset_minutes: 0 };
+            }
+            if let Self::Fixed { offset_minutes } = self {
+                ui.add(
+                    egui::DragValue::new(offset_minutes)
+                        .range(Self::OFFSET_MINUTES_RANGE)
+                        .speed(15)
+                        .custom_formatter(|minutes, _| {
+                            format_utc_offset(
+                                Self::Fixed {
+                                    offset_minutes: minutes as i32,
+                                }
+                                .offset_at(&Utc::now()),
+                            )
+                        }),
+                );
+            }
+        });
+    }
+}
+
+fn format_utc_offset(offset: FixedOffset) -> String {
+    let seconds = offset.local_minus_utc();
+    if seconds == 0 {
+        return "UTC".to_owned();
+    }
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.abs() / 60;
+    format!("UTC{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn test_fixed_offset_conversion() {
+        let utc = DateTime::from_timestamp(0, 0).unwrap();
+        let tz = DisplayTimeZone::Fixed {
+            offset_minutes: 120,
+        };
+        assert_eq!(tz.convert(utc).hour(), 2);
+        assert_eq!(tz.convert(utc), utc);
+    }
+
+    #[test]
+    fn test_time_zone_names() {
+        assert_eq!(DisplayTimeZone::Utc.name(), "UTC");
+        assert_eq!(
+            DisplayTimeZone::Fixed {
+                offset_minutes: 330
+            }
+            .name(),
+            "UTC+05:30"
+        );
+        assert_eq!(
+            DisplayTimeZone::Fixed {
+                offset_minutes: -90
+            }
+            .name(),
+            "UTC-01:30"
+        );
+    }
+}
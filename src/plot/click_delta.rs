@@ -7,45 +7,103 @@ use serde::{Deserialize, Serialize};
 use super::PlotType;
 
 /// Keeps track of clicks in plot areas to show the delta (x and y) of two different clicks.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct ClickDelta {
     // Which plot type the click belongs to
     plot_type: Option<PlotType>,
+    // The clicks as they are painted
     first_click: Option<[f64; 2]>,
     second_click: Option<[f64; 2]>,
+    // The y-values of the clicks in data units, i.e. without the log10 or secondary axis scaling of the painted y-values.
+    // Clicks restored from before they were recorded use the painted y-value
+    first_value_y: Option<f64>,
+    second_value_y: Option<f64>,
+    // Labels of the plots that the clicks were snapped to
+    first_snapped_to: Option<String>,
+    second_snapped_to: Option<String>,
     pixels_per_point: f64,
+    // Show the frequency (1/Δt) in the delta readout
+    show_frequency: bool,
+    // Show the slope (Δy/Δt) in the delta readout
+    show_slope: bool,
+    // Snap clicks to the closest sample of the closest plot
+    snap_to_data: bool,
+}
+
+impl Default for ClickDelta {
+    fn default() -> Self {
+        Self {
+            plot_type: None,
+            first_click: None,
+            second_click: None,
+            first_value_y: None,
+            second_value_y: None,
+            first_snapped_to: None,
+            second_snapped_to: None,
+            pixels_per_point: 0.0,
+            show_frequency: true,
+            show_slope: true,
+            snap_to_data: true,
+        }
+    }
 }
 
 impl ClickDelta {
-    pub fn set_next_click(&mut self, click: PlotPoint, plot_type: PlotType) {
+    /// Sets the next click, `click` is where it is painted and `value_y` is its y-value in data units.
+    ///
+    /// `snapped_to` is the label of the plot the click was snapped to if any.
+    pub fn set_next_click(
+        &mut self,
+        click: PlotPoint,
+        value_y: f64,
+        plot_type: PlotType,
+        snapped_to: Option<String>,
+    ) {
+        let click_point = [click.x, click.y];
         if self.plot_type.is_some_and(|pt| pt == plot_type) {
             if self.second_click.is_some() {
-                self.replace_first_click([click.x, click.y]);
-                self.second_click = None;
+                self.replace_first_click(click_point, value_y, snapped_to);
+                self.clear_second_click();
             } else if self.first_click.is_some() {
-                self.replace_second_click([click.x, click.y]);
+                self.replace_second_click(click_point, value_y, snapped_to);
             } else {
-                self.replace_first_click([click.x, click.y]);
+                self.replace_first_click(click_point, value_y, snapped_to);
             }
         } else {
             self.plot_type = Some(plot_type);
-            self.second_click = None;
-            self.replace_first_click([click.x, click.y]);
+            self.clear_second_click();
+            self.replace_first_click(click_point, value_y, snapped_to);
         }
     }
 
     pub fn reset(&mut self) {
         self.plot_type = None;
         self.first_click = None;
-        self.second_click = None;
+        self.first_value_y = None;
+        self.first_snapped_to = None;
+        self.clear_second_click();
     }
 
-    pub fn get_delta_text(a: [f64; 2], b: [f64; 2], plot_bounds: PlotBounds) -> egui_plot::Text {
+    /// Whether clicks should be snapped to the closest sample
+    pub fn snap_to_data(&self) -> bool {
+        self.snap_to_data
+    }
+
+    /// Returns the text with the delta between the painted points `a` and `b`, placed between them.
+    ///
+    /// The delta in y is calculated from `value_y`, the y-values of the points in data units.
+    pub fn get_delta_text(
+        &self,
+        a: [f64; 2],
+        b: [f64; 2],
+        value_y: [f64; 2],
+        plot_bounds: PlotBounds,
+    ) -> egui_plot::Text {
         let [x1, y1] = a;
         let [x2, y2] = b;
-        let delta_x = Self::delta_x(x1, x2);
+        let delta_formatted = self.delta_readout([x1, value_y[0]], [x2, value_y[1]]);
         let delta_y = y2 - y1;
-        let delta_formatted = crate::util::format_delta_xy(delta_x, delta_y);
         let x_offset = Self::calc_text_x_offset(x2, plot_bounds.range_x());
         let y_offset = Self::calc_text_y_offset(y2, plot_bounds.range_y());
         let dist_x = x2 - x1;
@@ -55,6 +113,35 @@ impl ClickDelta {
         egui_plot::Text::new(label_point, delta_formatted).highlight(true)
     }
 
+    /// Formats the delta between the points `a` and `b` (in data units) and the derived quantities that are enabled.
+    fn delta_readout(&self, a: [f64; 2], b: [f64; 2]) -> String {
+        let [x1, y1] = a;
+        let [x2, y2] = b;
+        let delta_x = Self::delta_x(x1, x2);
+        let mut delta_formatted = crate::util::format_delta_xy(delta_x, y2 - y1);
+        if self.show_frequency {
+            delta_formatted.push_str("\nf:");
+            match Self::frequency_hz(delta_x) {
+                Some(hz) => delta_formatted.push_str(&crate::util::format_frequency(hz)),
+                None => delta_formatted.push_str("n/a"),
+            }
+        }
+        if self.show_slope {
+            delta_formatted.push_str("\nΔy/Δt:");
+            match Self::slope(x1, y1, x2, y2) {
+                Some(slope) => delta_formatted.push_str(&format!("{slope:.4}/s")),
+                None => delta_formatted.push_str("n/a"),
+            }
+        }
+        if let Some(label) = &self.first_snapped_to {
+            delta_formatted.push_str(&format!("\n1: {label}"));
+        }
+        if let Some(label) = &self.second_snapped_to {
+            delta_formatted.push_str(&format!("\n2: {label}"));
+        }
+        delta_formatted
+    }
+
     pub fn plot_type(&self) -> Option<PlotType> {
         self.plot_type
     }
@@ -76,6 +163,25 @@ impl ClickDelta {
         (x2 - x1).abs() / 1_000_000_000.
     }
 
+    /// Returns the frequency in Hz of a period of `delta_t_s` seconds, or [`None`] if the period is zero
+    fn frequency_hz(delta_t_s: f64) -> Option<f64> {
+        (delta_t_s > 0.0).then(|| 1.0 / delta_t_s)
+    }
+
+    /// Returns the slope per second between the two points, or [`None`] if they have the same timestamp
+    fn slope(x1: f64, y1: f64, x2: f64, y2: f64) -> Option<f64> {
+        let delta_t_s = (x2 - x1) / 1_000_000_000.;
+        (delta_t_s != 0.0).then(|| (y2 - y1) / delta_t_s)
+    }
+
+    /// Shows the settings for which derived quantities to display in the delta readout
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.show_frequency, "Frequency (1/Δt)");
+        ui.checkbox(&mut self.show_slope, "Slope (Δy/Δt)");
+        ui.checkbox(&mut self.snap_to_data, "Snap to data")
+            .on_hover_text("Snap clicks to the closest sample of the closest plot. Hold Alt while clicking to place a point at the cursor");
+    }
+
     // Calculate the x offset for the text that describes the point delta
     fn calc_text_x_offset(x2: f64, range_x: RangeInclusive<f64>) -> f64 {
         let offset_factor = 50.;
@@ -104,12 +210,22 @@ impl ClickDelta {
         }
     }
 
-    fn replace_first_click(&mut self, click: [f64; 2]) {
+    fn replace_first_click(&mut self, click: [f64; 2], value_y: f64, snapped_to: Option<String>) {
         self.first_click = Some(click);
+        self.first_value_y = Some(value_y);
+        self.first_snapped_to = snapped_to;
     }
 
-    fn replace_second_click(&mut self, click: [f64; 2]) {
+    fn replace_second_click(&mut self, click: [f64; 2], value_y: f64, snapped_to: Option<String>) {
         self.second_click = Some(click);
+        self.second_value_y = Some(value_y);
+        self.second_snapped_to = snapped_to;
+    }
+
+    fn clear_second_click(&mut self) {
+        self.second_click = None;
+        self.second_value_y = None;
+        self.second_snapped_to = None;
     }
 
     fn delta_color(plot_ui_ctx: &egui::Context) -> Color32 {
@@ -119,7 +235,13 @@ impl ClickDelta {
         }
     }
 
-    pub fn ui(&self, plot_ui: &mut PlotUi, plot_type: PlotType) {
+    /// Paints the clicks and the delta between them, `pointer_value_y` maps the y-coordinate of the pointer to data units.
+    pub fn ui(
+        &self,
+        plot_ui: &mut PlotUi,
+        plot_type: PlotType,
+        pointer_value_y: impl Fn(f64) -> f64,
+    ) {
         // We only paint the click delta graphics on the plot type that matches the one that was clicked
         if self.plot_type().is_some_and(|pt| pt == plot_type) {
             if let Some(p) = self.get_click_points() {
@@ -134,8 +256,12 @@ impl ClickDelta {
                             let pcoord = [pointer_coord.x, pointer_coord.y];
                             let delta_line = Line::new(PlotPoints::new([fpoint, pcoord].into()))
                                 .color(delta_color);
+                            let value_y = [
+                                self.first_value_y.unwrap_or(fpoint[1]),
+                                pointer_value_y(pointer_coord.y),
+                            ];
 
-                            Self::ui_delta_line(plot_ui, delta_line, fpoint, pcoord);
+                            self.ui_delta_line(plot_ui, delta_line, [fpoint, pcoord], value_y);
                         }
                     }
                     (Some(fpoint), Some(spoint)) => {
@@ -143,16 +269,27 @@ impl ClickDelta {
                             .color(delta_color)
                             .name("Δ Click")
                             .style(egui_plot::LineStyle::Dashed { length: 10.0 });
+                        let value_y = [
+                            self.first_value_y.unwrap_or(fpoint[1]),
+                            self.second_value_y.unwrap_or(spoint[1]),
+                        ];
 
-                        Self::ui_delta_line(plot_ui, delta_line, fpoint, spoint);
+                        self.ui_delta_line(plot_ui, delta_line, [fpoint, spoint], value_y);
                     }
                 }
             }
         }
     }
 
-    fn ui_delta_line(plot_ui: &mut PlotUi, delta_line: Line, a: [f64; 2], b: [f64; 2]) {
-        let delta_text = Self::get_delta_text(a, b, plot_ui.plot_bounds());
+    fn ui_delta_line(
+        &self,
+        plot_ui: &mut PlotUi,
+        delta_line: Line,
+        points: [[f64; 2]; 2],
+        value_y: [f64; 2],
+    ) {
+        let [a, b] = points;
+        let delta_text = self.get_delta_text(a, b, value_y, plot_ui.plot_bounds());
         plot_ui.text(delta_text);
         plot_ui.add(delta_line);
     }
@@ -167,3 +304,50 @@ impl ClickDelta {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frequency_of_zero_delta_is_none() {
+        assert_eq!(ClickDelta::frequency_hz(0.0), None);
+        assert_eq!(ClickDelta::frequency_hz(0.02), Some(50.0));
+    }
+
+    #[test]
+    fn test_slope() {
+        let one_sec_ns = 1_000_000_000.;
+        assert_eq!(
+            ClickDelta::slope(0.0, 1.0, 2.0 * one_sec_ns, 5.0),
+            Some(2.0)
+        );
+        assert_eq!(
+            ClickDelta::slope(2.0 * one_sec_ns, 5.0, 0.0, 1.0),
+            Some(2.0)
+        );
+        assert_eq!(ClickDelta::slope(one_sec_ns, 1.0, one_sec_ns, 5.0), None);
+    }
+
+    #[test]
+    fn test_delta_is_in_data_units_on_log_axis() {
+        let one_sec_ns = 1_000_000_000.;
+        let log10 = plot_util::YTransform::log10(plot_util::NonPositiveValues::Skip);
+        let mut click_delta = ClickDelta::default();
+        for (x, value_y) in [(0.0, 10.0), (2.0 * one_sec_ns, 1000.0)] {
+            let painted = PlotPoint::new(x, log10.apply(value_y));
+            click_delta.set_next_click(painted, value_y, PlotType::Hundreds, None);
+        }
+        let (Some(a), Some(b)) = click_delta.get_click_coords() else {
+            panic!("Expected two clicks");
+        };
+        // The clicks are painted on the log axis
+        assert_eq!([a[1], b[1]], [1.0, 3.0]);
+        let readout = click_delta.delta_readout(
+            [a[0], click_delta.first_value_y.unwrap()],
+            [b[0], click_delta.second_value_y.unwrap()],
+        );
+        assert!(readout.contains("Δy:990.0000"), "{readout}");
+        assert!(readout.contains("Δy/Δt:495.0000/s"), "{readout}");
+    }
+}
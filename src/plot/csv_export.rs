@@ -0,0 +1,266 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, SecondsFormat};
+use egui::{Color32, RichText};
+use egui_phosphor::regular;
+use plot_util::{PlotValues, Plots};
+
+use super::{plot_settings::PlotSettings, PlotType};
+
+/// A window for exporting the shown series to CSV with a timestamp column and a column per series.
+///
+/// The series are exported with the start date offsets that are applied to them, i.e. as they are plotted.
+/// Rows have the union of the timestamps of the series, a series has an empty cell at the timestamps where it has no sample.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CsvExport {
+    open: bool,
+    file_per_plot_area: bool,
+    only_visible_range: bool,
+    err_msg: Option<String>,
+    exported: Vec<PathBuf>,
+}
+
+impl CsvExport {
+    pub fn toggle_open(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Shows the window if it is open, `visible_x_range` is the time range (in ns) currently shown in the plots.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        plots: &Plots,
+        plot_settings: &PlotSettings,
+        visible_x_range: Option<(f64, f64)>,
+    ) {
+        if !self.open {
+            return;
+        }
+        let mut open = self.open;
+        egui::Window::new(RichText::new(format!("{} Export to CSV", regular::EXPORT)))
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, plots, plot_settings, visible_x_range));
+        self.open = open;
+    }
+
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        plots: &Plots,
+        plot_settings: &PlotSettings,
+        visible_x_range: Option<(f64, f64)>,
+    ) {
+        ui.checkbox(&mut self.file_per_plot_area, "One file per plot area")
+            .on_hover_text("Write a file for each plot area instead of one file with all series");
+        ui.add_enabled(
+            visible_x_range.is_some(),
+            egui::Checkbox::new(&mut self.only_visible_range, "Only the visible time range"),
+        );
+        let groups = shown_series_by_plot_area(plots, plot_settings);
+        let series_count: usize = groups.iter().map(|(_, series)| series.len()).sum();
+        ui.label(format!("{series_count} shown series"));
+        if ui
+            .add_enabled(
+                series_count > 0,
+                egui::Button::new(format!("{} Export", regular::FLOPPY_DISK)),
+            )
+            .clicked()
+        {
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("CSV", &["csv"])
+                .set_file_name("plots.csv")
+                .save_file()
+            {
+                let x_range = visible_x_range.filter(|_| self.only_visible_range);
+                match self.export(&path, &groups, x_range) {
+                    Ok(exported) => {
+                        self.err_msg = None;
+                        self.exported = exported;
+                    }
+                    Err(e) => {
+                        self.err_msg = Some(format!("Failed exporting to CSV: {e}"));
+                        self.exported.clear();
+                    }
+                }
+            }
+        }
+        if let Some(err_msg) = &self.err_msg {
+            ui.label(RichText::new(format!("⚠ {err_msg}")).color(Color32::YELLOW));
+        }
+        for path in &self.exported {
+            ui.label(format!("Exported {}", path.display()));
+        }
+    }
+
+    /// Writes the `groups` of series to `path`, or to a file per group next to `path` and returns the paths of the written files
+    fn export(
+        &self,
+        path: &Path,
+        groups: &[(PlotType, Vec<&PlotValues>)],
+        x_range: Option<(f64, f64)>,
+    ) -> io::Result<Vec<PathBuf>> {
+        if !self.file_per_plot_area {
+            let series: Vec<&PlotValues> = groups
+                .iter()
+                .flat_map(|(_, series)| series.iter().copied())
+                .collect();
+            write_csv_file(path, &series, x_range)?;
+            return Ok(vec![path.to_owned()]);
+        }
+        let stem = path
+            .file_stem()
+            .map_or_else(|| "plots".into(), |s| s.to_string_lossy());
+        let mut exported = Vec::new();
+        for (plot_type, series) in groups.iter().filter(|(_, s)| !s.is_empty()) {
+            let group_path = path.with_file_name(format!(
+                "{stem}_{}.csv",
+                plot_type.to_string().to_lowercase()
+            ));
+            write_csv_file(&group_path, series, x_range)?;
+            exported.push(group_path);
+        }
+        Ok(exported)
+    }
+}
+
+fn shown_series_by_plot_area<'a>(
+    plots: &'a Plots,
+    plot_settings: &'a PlotSettings,
+) -> Vec<(PlotType, Vec<&'a PlotValues>)> {
+    [
+        (PlotType::Percentage, plots.percentage()),
+        (PlotType::Hundreds, plots.one_to_hundred()),
+        (PlotType::Thousands, plots.thousands()),
+    ]
+    .into_iter()
+    .map(|(plot_type, plot_data)| {
+        (
+            plot_type,
+            plot_settings.apply_filters(plot_data.plots()).collect(),
+        )
+    })
+    .collect()
+}
+
+fn write_csv_file(
+    path: &Path,
+    series: &[&PlotValues],
+    x_range: Option<(f64, f64)>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(fs::File::create(path)?);
+    write_csv(&mut writer, series, x_range)?;
+    writer.flush()
+}
+
+/// Writes the `series` as CSV with a timestamp column (RFC 3339, UTC) and a column per series.
+///
+/// If `x_range` is given, only the samples in the range (in ns) are written.
+fn write_csv(
+    writer: &mut impl Write,
+    series: &[&PlotValues],
+    x_range: Option<(f64, f64)>,
+) -> io::Result<()> {
+    let in_range =
+        |point: &&[f64; 2]| x_range.is_none_or(|(min, max)| (min..=max).contains(&point[0]));
+    let points: Vec<Vec<[f64; 2]>> = series
+        .iter()
+        .map(|pv| pv.raw_plot().iter().filter(in_range).copied().collect())
+        .collect();
+    let mut timestamps: Vec<f64> = points.iter().flatten().map(|p| p[0]).collect();
+    timestamps.sort_unstable_by(f64::total_cmp);
+    timestamps.dedup();
+
+    write!(writer, "timestamp")?;
+    for pv in series {
+        let header = match pv.get_unit() {
+            Some(unit) => format!("{} [{unit}]", pv.label()),
+            None => pv.label().to_owned(),
+        };
+        write!(writer, ",{}", csv_field(&header))?;
+    }
+    writeln!(writer)?;
+
+    // The samples of each series are sorted by time, so each series is walked once
+    let mut next_idx = vec![0; points.len()];
+    for t in timestamps {
+        let dt = DateTime::from_timestamp_nanos(t as i64);
+        write!(
+            writer,
+            "{}",
+            dt.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        )?;
+        for (series_points, idx) in points.iter().zip(next_idx.iter_mut()) {
+            write!(writer, ",")?;
+            if series_points.get(*idx).is_some_and(|p| p[0] == t) {
+                write!(writer, "{}", series_points[*idx][1])?;
+            }
+            // Skip later samples with the same timestamp
+            while series_points.get(*idx).is_some_and(|p| p[0] <= t) {
+                *idx += 1;
+            }
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+/// Quotes the `field` if it contains characters that have a meaning in CSV
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv_string(series: &[&PlotValues], x_range: Option<(f64, f64)>) -> String {
+        let mut buf = Vec::new();
+        write_csv(&mut buf, series, x_range).expect("writing to a Vec can't fail");
+        String::from_utf8(buf).expect("CSV is UTF-8")
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let a = PlotValues::new(vec![[0.0, 1.0], [1_000_000_000.0, 2.0]], "RPM".into(), 1);
+        let b = PlotValues::new(
+            vec![[1_000_000_000.0, 0.5], [2_500_000_000.0, 0.25]],
+            "Duty, cycle".into(),
+            2,
+        )
+        .unit(Some("%".into()));
+        let csv = csv_string(&[&a, &b], None);
+        let expected_header = format!("timestamp,{},\"{} [%]\"", a.label(), b.label());
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(expected_header.as_str()));
+        assert_eq!(lines.next(), Some("1970-01-01T00:00:00Z,1,"));
+        assert_eq!(lines.next(), Some("1970-01-01T00:00:01Z,2,0.5"));
+        assert_eq!(lines.next(), Some("1970-01-01T00:00:02.500Z,,0.25"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_write_csv_only_range() {
+        let a = PlotValues::new(
+            (0..10)
+                .map(|i| [f64::from(i) * 1e9, f64::from(i)])
+                .collect(),
+            "RPM".into(),
+            1,
+        );
+        let csv = csv_string(&[&a], Some((2e9, 4e9)));
+        let values: Vec<&str> = csv
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split(',').nth(1))
+            .collect();
+        assert_eq!(values, ["2", "3", "4"]);
+    }
+}
@@ -0,0 +1,193 @@
+use chrono::DateTime;
+use egui::RichText;
+use egui_phosphor::regular;
+use plot_util::{PlotValues, Plots};
+
+use super::{
+    axis_config::{AxisConfig, DisplayTimeZone},
+    plot_settings::PlotSettings,
+    util,
+};
+
+/// A time gap between two consecutive samples of a series, in nanoseconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Gap {
+    start: f64,
+    end: f64,
+}
+
+impl Gap {
+    fn duration_s(&self) -> f64 {
+        (self.end - self.start) / 1_000_000_000.0
+    }
+}
+
+/// A window for finding the gaps in a series, e.g. dropouts in telemetry, and zooming to them.
+///
+/// A gap is an interval between two samples that is longer than a multiple of the median interval of the series.
+#[derive(Debug, PartialEq)]
+pub struct GapFinder {
+    open: bool,
+    series_label: Option<String>,
+    median_multiple: f64,
+    // The series the gaps were found in and the gaps, they are only searched for on demand as it can be slow for large series
+    found_gaps: Option<(String, Vec<Gap>)>,
+}
+
+impl Default for GapFinder {
+    fn default() -> Self {
+        Self {
+            open: false,
+            series_label: None,
+            median_multiple: 5.0,
+            found_gaps: None,
+        }
+    }
+}
+
+impl GapFinder {
+    pub fn toggle_open(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Shows the window if it is open, zooming to a gap is requested through the `axis_config`.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        plots: &Plots,
+        plot_settings: &PlotSettings,
+        axis_config: &mut AxisConfig,
+    ) {
+        if !self.open {
+            return;
+        }
+        let series = util::shown_series(plots, plot_settings);
+        let mut open = self.open;
+        egui::Window::new(RichText::new(format!("{} Gaps", regular::LINK_BREAK)))
+            .open(&mut open)
+            .show(ctx, |ui| self.ui(ui, &series, axis_config));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, series: &[&PlotValues], axis_config: &mut AxisConfig) {
+        ui.horizontal(|ui| {
+            util::series_combo_box(ui, "gap_finder_series", &mut self.series_label, series);
+            ui.label("Longer than");
+            ui.add(
+                egui::DragValue::new(&mut self.median_multiple)
+                    .speed(0.1)
+                    .range(1.0..=1000.0)
+                    .suffix("×"),
+            )
+            .on_hover_text("Multiple of the median interval between samples of the series");
+            let selected = self
+                .series_label
+                .as_deref()
+                .and_then(|label| series.iter().find(|pv| pv.label() == label));
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new("Find gaps"))
+                .clicked()
+            {
+                if let Some(pv) = selected {
+                    self.found_gaps = Some((
+                        pv.label().to_owned(),
+                        find_gaps(pv.raw_plot(), self.median_multiple),
+                    ));
+                }
+            }
+        });
+        let Some((label, gaps)) = &self.found_gaps else {
+            return;
+        };
+        ui.separator();
+        if gaps.is_empty() {
+            ui.label(format!("No gaps found in {label}"));
+            return;
+        }
+        ui.label(format!("{} gaps found in {label}", gaps.len()));
+        let time_zone = axis_config.time_zone();
+        let mut zoom_to = None;
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                egui::Grid::new("gaps").striped(true).show(ui, |ui| {
+                    for gap in gaps {
+                        ui.label(format_time_ns(gap.start, time_zone));
+                        ui.label(crate::util::format_time_s(gap.duration_s()));
+                        if ui
+                            .button(regular::MAGNIFYING_GLASS)
+                            .on_hover_text("Zoom to the gap")
+                            .clicked()
+                        {
+                            zoom_to = Some(*gap);
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        if let Some(Gap { start, end }) = zoom_to {
+            // Show the samples on both sides of the gap
+            let margin = end - start;
+            axis_config.request_zoom_x(start - margin, end + margin);
+        }
+    }
+}
+
+fn format_time_ns(time_ns: f64, time_zone: DisplayTimeZone) -> String {
+    let dt = DateTime::from_timestamp_nanos(time_ns as i64);
+    time_zone
+        .convert(dt)
+        .format("%Y-%m-%d %H:%M:%S%.3f")
+        .to_string()
+}
+
+/// Returns the gaps between the `points` (sorted by time) that are longer than `median_multiple` times the median interval.
+///
+/// Points with the same timestamp are not counted when finding the median interval, so duplicates don't make everything a gap.
+fn find_gaps(points: &[[f64; 2]], median_multiple: f64) -> Vec<Gap> {
+    let mut intervals: Vec<f64> = points
+        .windows(2)
+        .map(|w| w[1][0] - w[0][0])
+        .filter(|dt| *dt > 0.0)
+        .collect();
+    if intervals.is_empty() {
+        return Vec::new();
+    }
+    let mid = intervals.len() / 2;
+    let (_, median, _) = intervals.select_nth_unstable_by(mid, f64::total_cmp);
+    let threshold = *median * median_multiple;
+    points
+        .windows(2)
+        .filter(|w| w[1][0] - w[0][0] > threshold)
+        .map(|w| Gap {
+            start: w[0][0],
+            end: w[1][0],
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_gaps() {
+        let mut points: Vec<[f64; 2]> = (0..10).map(|i| [f64::from(i), 0.0]).collect();
+        points.extend((20..30).map(|i| [f64::from(i), 0.0]));
+        assert_eq!(
+            find_gaps(&points, 5.0),
+            [Gap {
+                start: 9.0,
+                end: 20.0
+            }]
+        );
+        assert!(find_gaps(&points, 20.0).is_empty());
+    }
+
+    #[test]
+    fn test_find_gaps_few_points() {
+        assert!(find_gaps(&[], 5.0).is_empty());
+        assert!(find_gaps(&[[1.0, 0.0]], 5.0).is_empty());
+        assert!(find_gaps(&[[1.0, 0.0], [1.0, 1.0]], 5.0).is_empty());
+    }
+}
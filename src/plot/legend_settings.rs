@@ -0,0 +1,213 @@
+use std::collections::BTreeSet;
+
+use egui::{text::LayoutJob, Align, Color32, FontSelection, Layout, RichText, Sense, TextStyle};
+use egui_phosphor::regular;
+use egui_plot::{Corner, Legend};
+use serde::{Deserialize, Serialize};
+
+/// Settings for the legend that is shown in each plot area.
+#[allow(
+    missing_debug_implementations,
+    reason = "Legend is from egui_plot and doesn't implement debug"
+)]
+#[derive(Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LegendSettings {
+    legend: Legend,
+    show_legend: bool,
+    // Plot areas with more entries than this get a legend that scrolls, so it doesn't cover the plots
+    max_entries: usize,
+}
+
+impl Default for LegendSettings {
+    fn default() -> Self {
+        Self {
+            legend: Legend::default(),
+            show_legend: true,
+            max_entries: 10,
+        }
+    }
+}
+
+impl LegendSettings {
+    /// Returns the [`Legend`] to add to a plot area with `entry_count` entries or [`None`] if the legend is hidden or scrolls
+    pub fn legend(&self, entry_count: usize) -> Option<&Legend> {
+        (self.show_legend && !self.scrolls(entry_count)).then_some(&self.legend)
+    }
+
+    /// Whether a plot area with `entry_count` entries has a [`ScrollLegend`] instead of the [`Legend`] of `egui_plot`
+    pub fn scrolls(&self, entry_count: usize) -> bool {
+        self.show_legend && entry_count > self.max_entries
+    }
+
+    fn corner_name(corner: Corner) -> &'static str {
+        match corner {
+            Corner::LeftTop => "Top left",
+            Corner::RightTop => "Top right",
+            Corner::LeftBottom => "Bottom left",
+            Corner::RightBottom => "Bottom right",
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button(format!("{} Legend", regular::LIST_BULLETS), |ui| {
+            ui.checkbox(&mut self.show_legend, "Show legend");
+            ui.add_enabled_ui(self.show_legend, |ui| {
+                egui::ComboBox::from_label("Position")
+                    .selected_text(Self::corner_name(self.legend.position))
+                    .show_ui(ui, |ui| {
+                        for corner in Corner::all() {
+                            ui.selectable_value(
+                                &mut self.legend.position,
+                                corner,
+                                Self::corner_name(corner),
+                            );
+                        }
+                    });
+                ui.add(
+                    egui::Slider::new(&mut self.legend.background_alpha, 0.0..=1.0)
+                        .text("Background opacity"),
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Text size");
+                    ui.radio_value(&mut self.legend.text_style, TextStyle::Small, "Small");
+                    ui.radio_value(&mut self.legend.text_style, TextStyle::Body, "Normal");
+                });
+                ui.add(
+                    egui::Slider::new(&mut self.max_entries, 1..=50).text("Max entries"),
+                )
+                .on_hover_text(
+                    "The legend of plot areas with more plots than this scrolls instead of covering the plots",
+                );
+            });
+        });
+    }
+}
+
+/// A legend that scrolls, for plot areas with too many plots to fit the [`Legend`] of `egui_plot` without covering them.
+///
+/// Like the legend of `egui_plot`, clicking an entry hides or shows its plot and hovering an entry highlights its plot.
+#[derive(Debug, Clone, Default)]
+pub struct ScrollLegend {
+    hidden: BTreeSet<String>,
+    hovered: Option<String>,
+}
+
+impl ScrollLegend {
+    /// Loads the state of the legend with the `id` from the previous frame
+    pub fn load(ctx: &egui::Context, id: egui::Id) -> Self {
+        ctx.data(|d| d.get_temp(id)).unwrap_or_default()
+    }
+
+    pub fn is_hidden(&self, label: &str) -> bool {
+        self.hidden.contains(label)
+    }
+
+    pub fn is_hovered(&self, label: &str) -> bool {
+        self.hovered.as_deref() == Some(label)
+    }
+
+    /// Shows the `entries` (label and color) in the corner of the plot area `rect` and stores the state for the next frame
+    pub fn show(
+        mut self,
+        ui: &mut egui::Ui,
+        id: egui::Id,
+        rect: egui::Rect,
+        settings: &LegendSettings,
+        entries: &[(String, Color32)],
+    ) {
+        let legend = &settings.legend;
+        let (main_dir, cross_align) = match legend.position {
+            Corner::LeftTop => (egui::Direction::TopDown, Align::LEFT),
+            Corner::RightTop => (egui::Direction::TopDown, Align::RIGHT),
+            Corner::LeftBottom => (egui::Direction::BottomUp, Align::LEFT),
+            Corner::RightBottom => (egui::Direction::BottomUp, Align::RIGHT),
+        };
+        let mut legend_ui = ui.new_child(
+            egui::UiBuilder::new()
+                .max_rect(rect.shrink(4.0))
+                .layout(Layout::from_main_dir_and_cross_align(main_dir, cross_align)),
+        );
+        let font_id = legend.text_style.resolve(legend_ui.style());
+        let row_height =
+            legend_ui.fonts(|f| f.row_height(&font_id)) + legend_ui.spacing().item_spacing.y;
+        let max_height =
+            (settings.max_entries as f32 * row_height).min(legend_ui.available_height() - 8.0);
+        self.hovered = None;
+        // The same frame as the legend of egui_plot
+        egui::Frame {
+            inner_margin: egui::vec2(8.0, 4.0).into(),
+            rounding: legend_ui.visuals().window_rounding,
+            fill: legend_ui.visuals().extreme_bg_color,
+            stroke: legend_ui.visuals().window_stroke(),
+            ..Default::default()
+        }
+        .multiply_with_opacity(legend.background_alpha)
+        .show(&mut legend_ui, |ui| {
+            egui::ScrollArea::vertical()
+                .id_salt(id)
+                .max_height(max_height)
+                .show(ui, |ui| {
+                    ui.with_layout(Layout::top_down(cross_align), |ui| {
+                        for (label, color) in entries {
+                            self.entry_ui(ui, label, *color, &legend.text_style);
+                        }
+                    });
+                });
+        });
+        ui.ctx().data_mut(|d| d.insert_temp(id, self));
+    }
+
+    fn entry_ui(&mut self, ui: &mut egui::Ui, label: &str, color: Color32, text_style: &TextStyle) {
+        let hidden = self.is_hidden(label);
+        let (icon, icon_color, text_color) = if hidden {
+            let weak = ui.visuals().weak_text_color();
+            (regular::CIRCLE, weak, weak)
+        } else {
+            (regular::RECORD, color, ui.visuals().text_color())
+        };
+        let mut job = LayoutJob::default();
+        for (text, color) in [
+            (format!("{icon} "), icon_color),
+            (label.to_owned(), text_color),
+        ] {
+            RichText::new(text)
+                .text_style(text_style.clone())
+                .color(color)
+                .append_to(&mut job, ui.style(), FontSelection::Default, Align::Center);
+        }
+        let resp = ui.add(
+            egui::Label::new(job)
+                .selectable(false)
+                .sense(Sense::click()),
+        );
+        if resp.clicked() {
+            if hidden {
+                self.hidden.remove(label);
+            } else {
+                self.hidden.insert(label.to_owned());
+            }
+        }
+        if resp.hovered() {
+            self.hovered = Some(label.to_owned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legend_scrolls_with_many_entries() {
+        let mut settings = LegendSettings::default();
+        assert!(settings.legend(10).is_some());
+        assert!(!settings.scrolls(10));
+        assert!(settings.legend(11).is_none());
+        assert!(settings.scrolls(11));
+
+        settings.show_legend = false;
+        assert!(settings.legend(1).is_none());
+        assert!(!settings.scrolls(11));
+    }
+}
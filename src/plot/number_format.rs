@@ -0,0 +1,72 @@
+use egui_phosphor::regular;
+use serde::{Deserialize, Serialize};
+
+/// How counts, e.g. the number of loaded data points, are displayed.
+///
+/// The default is `1,234,567`, some locales use spaces or periods to separate thousands or prefer SI prefixes like `1.23 M`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub thousands_separator: char,
+    pub decimal_separator: char,
+    /// Show numbers of a thousand or more with an SI prefix and two decimals instead of all digits
+    pub si_prefix: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            thousands_separator: ',',
+            decimal_separator: '.',
+            si_prefix: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    const THOUSANDS_SEPARATORS: [(char, &'static str); 4] = [
+        (',', "Comma"),
+        ('.', "Period"),
+        (' ', "Space"),
+        ('\'', "Apostrophe"),
+    ];
+    const DECIMAL_SEPARATORS: [(char, &'static str); 2] = [('.', "Period"), (',', "Comma")];
+
+    fn separator_name(separators: &[(char, &'static str)], separator: char) -> &'static str {
+        separators
+            .iter()
+            .find_map(|(c, name)| (*c == separator).then_some(*name))
+            .unwrap_or("Custom")
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.menu_button(format!("{} Numbers", regular::HASH), |ui| {
+            ui.checkbox(&mut self.si_prefix, "SI prefixes")
+                .on_hover_text("Show counts like 1.23 M instead of 1,234,567");
+            ui.add_enabled_ui(!self.si_prefix, |ui| {
+                egui::ComboBox::from_label("Thousands separator")
+                    .selected_text(Self::separator_name(
+                        &Self::THOUSANDS_SEPARATORS,
+                        self.thousands_separator,
+                    ))
+                    .show_ui(ui, |ui| {
+                        for (separator, name) in Self::THOUSANDS_SEPARATORS {
+                            ui.selectable_value(&mut self.thousands_separator, separator, name);
+                        }
+                    });
+            });
+            ui.add_enabled_ui(self.si_prefix, |ui| {
+                egui::ComboBox::from_label("Decimal separator")
+                    .selected_text(Self::separator_name(
+                        &Self::DECIMAL_SEPARATORS,
+                        self.decimal_separator,
+                    ))
+                    .show_ui(ui, |ui| {
+                        for (separator, name) in Self::DECIMAL_SEPARATORS {
+                            ui.selectable_value(&mut self.decimal_separator, separator, name);
+                        }
+                    });
+            });
+        });
+    }
+}
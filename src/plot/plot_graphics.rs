@@ -1,8 +1,15 @@
-use egui::Vec2b;
-use egui_plot::{AxisHints, HPlacement, Legend, Plot};
-use plot_util::{PlotData, Plots};
+use egui::{Color32, Vec2b};
+use egui_phosphor::regular;
+use egui_plot::{AxisHints, GridMark, HPlacement, Plot, PlotBounds, PlotPoint, VLine};
+use plot_util::{PlotData, PlotValues, Plots, YTransform};
 
-use super::{axis_config::AxisConfig, plot_settings::PlotSettings, ClickDelta, PlotType};
+use super::{
+    axis_config::{format_log10_tick, format_log10_value, AxisConfig},
+    legend_settings::{LegendSettings, ScrollLegend},
+    plot_settings::PlotSettings,
+    sample_cursor::SampleCursor,
+    ClickDelta, PlotType,
+};
 
 /// Paints multiple plots based on the provided settings and configurations.
 ///
@@ -11,11 +18,13 @@ use super::{axis_config::AxisConfig, plot_settings::PlotSettings, ClickDelta, Pl
 /// * `ui` - The egui UI to paint on.
 /// * `plots` - The [`Plots`] struct containing plot data.
 /// * `plot_settings` - Controls plot display.
-/// * `legend_cfg` - Legend configuration.
+/// * `legend_cfg` - Legend settings.
 /// * `axis_cfg` - For axis customization.
 /// * `link_group` - An [`egui::Id`] for linking plots.
 /// * `line_width` - The width of plot lines.
 /// * `click_delta` - State relating to pointer clicks on plots
+/// * `sample_cursor` - The cursor that is moved between samples with the arrow keys
+/// * `series_subset` - If set, only the plots with these names are painted.
 #[allow(
     clippy::too_many_arguments,
     reason = "They are needed. Maybe a refactor could group some of them."
@@ -24,42 +33,62 @@ pub fn paint_plots(
     ui: &mut egui::Ui,
     plots: &mut Plots,
     plot_settings: &PlotSettings,
-    legend_cfg: &Legend,
+    legend_cfg: &LegendSettings,
     axis_cfg: &mut AxisConfig,
     link_group: egui::Id,
     line_width: f32,
     click_delta: &mut ClickDelta,
+    sample_cursor: &mut SampleCursor,
+    series_subset: Option<&[String]>,
 ) {
     let plot_height = ui.available_height() / (plot_settings.total_plot_count() as f32);
+    let reset_zoom = axis_cfg.reset_zoom_requested();
 
-    let x_axes = vec![AxisHints::new_x().formatter(crate::util::format_time)];
+    let time_zone = axis_cfg.time_zone();
+    let x_axes = vec![AxisHints::new_x()
+        .label(format!("Time ({})", time_zone.name()))
+        .formatter(move |mark, _range| crate::util::format_time(mark, time_zone))];
+
+    let secondary_y_transform = |plot_data: &PlotData, plot_type: PlotType| {
+        secondary_y_transform(axis_cfg, plot_settings, plot_data, plot_type, series_subset)
+    };
+    let percentage_y_transform = secondary_y_transform(plots.percentage(), PlotType::Percentage);
+    let to_hundred_y_transform = secondary_y_transform(plots.one_to_hundred(), PlotType::Hundreds);
+    let thousands_y_transform = secondary_y_transform(plots.thousands(), PlotType::Thousands);
+    let y_unit = |plot_data: &PlotData| {
+        primary_y_axis_unit(axis_cfg, plot_settings, plot_data, series_subset)
+    };
 
     let percentage_plot = build_plot_ui(
         "percentage",
+        PlotType::Percentage,
         plot_height,
-        legend_cfg.clone(),
         axis_cfg,
         x_axes.clone(),
         link_group,
-    )
-    .include_y(1.0)
-    .y_axis_formatter(|y, _range| format!("{:.0}%", y.value * 100.0));
+        percentage_y_transform,
+        y_unit(plots.percentage()),
+    );
 
     let to_hundred_plot = build_plot_ui(
         "to_hundred",
+        PlotType::Hundreds,
         plot_height,
-        legend_cfg.clone(),
         axis_cfg,
         x_axes.clone(),
         link_group,
+        to_hundred_y_transform,
+        y_unit(plots.one_to_hundred()),
     );
     let thousands_plot: Plot<'_> = build_plot_ui(
         "thousands",
+        PlotType::Thousands,
         plot_height,
-        legend_cfg.clone(),
         axis_cfg,
         x_axes,
         link_group,
+        thousands_y_transform,
+        y_unit(plots.thousands()),
     );
     let mut plot_components_list = Vec::with_capacity(plot_settings.total_plot_count().into());
 
@@ -70,15 +99,30 @@ pub fn paint_plots(
     } = plots;
 
     if plot_settings.display_percentage() {
-        plot_components_list.push((percentage_plot, percentage, PlotType::Percentage));
+        plot_components_list.push((
+            percentage_plot,
+            percentage,
+            PlotType::Percentage,
+            percentage_y_transform,
+        ));
     }
 
     if plot_settings.display_hundreds() {
-        plot_components_list.push((to_hundred_plot, one_to_hundred, PlotType::Hundreds));
+        plot_components_list.push((
+            to_hundred_plot,
+            one_to_hundred,
+            PlotType::Hundreds,
+            to_hundred_y_transform,
+        ));
     }
 
     if plot_settings.display_thousands() {
-        plot_components_list.push((thousands_plot, thousands, PlotType::Thousands));
+        plot_components_list.push((
+            thousands_plot,
+            thousands,
+            PlotType::Thousands,
+            thousands_y_transform,
+        ));
     }
 
     fill_plots(
@@ -87,7 +131,11 @@ pub fn paint_plots(
         axis_cfg,
         line_width,
         plot_settings,
+        legend_cfg,
         click_delta,
+        sample_cursor,
+        reset_zoom,
+        series_subset,
     );
 }
 
@@ -96,72 +144,416 @@ pub fn paint_plots(
 /// # Arguments
 ///
 /// * `gui` - The egui UI to paint on.
-/// * `plot_components` - A vector of tuples containing [`Plot`], [`PlotData`], and [`PlotType`].
+/// * `plot_components` - A vector of tuples containing [`Plot`], [`PlotData`], [`PlotType`], and the secondary y-axis [`YTransform`] (if any).
 /// * `axis_config` - For axis customization.
 /// * `line_width` - The width of plot lines.
 /// * `plot_settings` - Controls which plots to display.
+/// * `legend_cfg` - Legend settings, plot areas with too many plots for the [`egui_plot::Legend`] get a [`ScrollLegend`].
+/// * `click_delta` - State relating to pointer clicks on plots
+/// * `sample_cursor` - The cursor that is moved between samples with the arrow keys
+/// * `reset_zoom` - Whether to reset the bounds of all plots to fit their data.
+/// * `series_subset` - If set, only the plots with these names are painted.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "They are needed. Maybe a refactor could group some of them."
+)]
 fn fill_plots(
     gui: &mut egui::Ui,
-    plot_components: Vec<(Plot<'_>, &mut PlotData, PlotType)>,
+    plot_components: Vec<(Plot<'_>, &mut PlotData, PlotType, Option<YTransform>)>,
     axis_config: &mut AxisConfig,
     line_width: f32,
     plot_settings: &PlotSettings,
+    legend_cfg: &LegendSettings,
     click_delta: &mut ClickDelta,
+    sample_cursor: &mut SampleCursor,
+    reset_zoom: bool,
+    series_subset: Option<&[String]>,
 ) {
-    for (ui, plot, ptype) in plot_components {
-        ui.show(gui, |plot_ui| {
+    for (mut ui, plot, ptype, secondary_y_transform) in plot_components {
+        let legend_entries = legend_entries(plot_settings, plot, series_subset);
+        if let Some(legend) = legend_cfg.legend(legend_entries.len()) {
+            ui = ui.legend(legend.clone());
+        }
+        let legend_id = gui.id().with(("scroll_legend", ptype));
+        let scroll_legend = legend_cfg
+            .scrolls(legend_entries.len())
+            .then(|| ScrollLegend::load(gui.ctx(), legend_id));
+        let plot_resp = ui.show(gui, |plot_ui| {
             let resp = plot_ui.response();
             if resp.clicked() {
-                if plot_ui.ctx().input(|i| i.modifiers.shift) {
+                let modifiers = plot_ui.ctx().input(|i| i.modifiers);
+                if modifiers.shift {
                     if let Some(pointer_coordinate) = plot_ui.pointer_coordinate() {
-                        click_delta.set_next_click(pointer_coordinate, ptype);
+                        let closest = if click_delta.snap_to_data() && !modifiers.alt {
+                            closest_sample(
+                                plot_ui,
+                                plot,
+                                pointer_coordinate,
+                                series_y_transform(
+                                    axis_config,
+                                    plot_settings,
+                                    ptype,
+                                    secondary_y_transform,
+                                ),
+                                plot_settings,
+                                series_subset,
+                            )
+                        } else {
+                            None
+                        };
+                        match closest {
+                            Some((point, value_y, pv)) => {
+                                let label = plot_settings.series_label(pv).into_owned();
+                                click_delta.set_next_click(point, value_y, ptype, Some(label));
+                            }
+                            None => click_delta.set_next_click(
+                                pointer_coordinate,
+                                primary_y_value(axis_config, ptype, pointer_coordinate.y),
+                                ptype,
+                                None,
+                            ),
+                        }
                     }
                 } else {
                     click_delta.reset();
                 }
             }
-            click_delta.ui(plot_ui, ptype);
+            click_delta.ui(plot_ui, ptype, |y| primary_y_value(axis_config, ptype, y));
+            copy_sample_context_menu(
+                plot_ui,
+                (plot, ptype),
+                secondary_y_transform,
+                axis_config,
+                plot_settings,
+                series_subset,
+            );
 
             fill_plot(
                 plot_ui,
                 (plot, ptype),
+                secondary_y_transform,
                 axis_config,
                 line_width,
                 plot_settings,
+                reset_zoom,
+                scroll_legend.as_ref(),
+                series_subset,
+            );
+
+            sample_cursor_ui(
+                plot_ui,
+                sample_cursor,
+                (plot, ptype),
+                secondary_y_transform,
+                axis_config,
+                plot_settings,
+                series_subset,
             );
         });
+        if let Some(scroll_legend) = scroll_legend {
+            scroll_legend.show(
+                gui,
+                legend_id,
+                plot_resp.response.rect,
+                legend_cfg,
+                &legend_entries,
+            );
+        }
     }
 }
 
+/// Shows a context menu on right click for copying the sample closest to the pointer to the clipboard.
+fn copy_sample_context_menu(
+    plot_ui: &egui_plot::PlotUi,
+    plot: (&PlotData, PlotType),
+    secondary_y_transform: Option<YTransform>,
+    axis_config: &AxisConfig,
+    plot_settings: &PlotSettings,
+    series_subset: Option<&[String]>,
+) {
+    let (plot_data, plot_type) = plot;
+    let resp = plot_ui.response();
+    // The sample is found when the menu is opened, as the pointer moves to the menu afterwards
+    if resp.secondary_clicked() {
+        let y_transform =
+            series_y_transform(axis_config, plot_settings, plot_type, secondary_y_transform);
+        let sample_text = plot_ui.pointer_coordinate().and_then(|pointer| {
+            // The value is in the unit it is converted to (if any), as it is shown in the label and the legend
+            let (point, value_y, pv) = closest_sample(
+                plot_ui,
+                plot_data,
+                pointer,
+                &y_transform,
+                plot_settings,
+                series_subset,
+            )?;
+            Some(crate::util::format_sample_for_clipboard(
+                &plot_settings.series_label(pv),
+                point.x,
+                value_y,
+            ))
+        });
+        plot_ui
+            .ctx()
+            .data_mut(|d| d.insert_temp(resp.id, sample_text));
+    }
+    resp.context_menu(|ui| {
+        let sample_text = ui
+            .ctx()
+            .data(|d| d.get_temp::<Option<String>>(resp.id))
+            .flatten();
+        match sample_text {
+            Some(text) => {
+                if ui
+                    .button(format!("{} Copy closest sample", regular::COPY))
+                    .on_hover_text(&text)
+                    .clicked()
+                {
+                    ui.ctx().copy_text(text);
+                    ui.close_menu();
+                }
+            }
+            None => {
+                ui.label("No sample close to the pointer");
+            }
+        }
+    });
+}
+
+/// Moves the [`SampleCursor`] according to the keyboard input and paints it if it is in the plot area.
+fn sample_cursor_ui(
+    plot_ui: &mut egui_plot::PlotUi,
+    sample_cursor: &mut SampleCursor,
+    plot: (&PlotData, PlotType),
+    secondary_y_transform: Option<YTransform>,
+    axis_config: &AxisConfig,
+    plot_settings: &PlotSettings,
+    series_subset: Option<&[String]>,
+) {
+    let (plot_data, plot_type) = plot;
+    let plots: Vec<&PlotValues> = shown_plots(plot_settings, plot_data, series_subset).collect();
+    sample_cursor.handle_input(plot_ui, plot_type, &plots);
+    let Some((pv, [x, y])) = sample_cursor.selected_sample(plot_type, &plots) else {
+        return;
+    };
+    let y_transform =
+        series_y_transform(axis_config, plot_settings, plot_type, secondary_y_transform)(pv);
+    let painted_y = y_transform.map_or(y, |t| t.apply(y));
+    // The readout shows the value in the unit it is converted to (if any), not the scaled value on the secondary axis
+    let readout_y = plot_settings.unit_conversion(pv.name()).map_or(y, |c| {
+        let (scale, offset) = c.scale_offset();
+        y * scale + offset
+    });
+    let readout = crate::util::format_label_ns(
+        &plot_settings.series_label(pv),
+        &PlotPoint::new(x, readout_y),
+        axis_config.time_zone(),
+    );
+    SampleCursor::ui(plot_ui, PlotPoint::new(x, painted_y), readout);
+}
+
 /// Fills and paints a single plot with its data.
 ///
 /// # Arguments
 ///
 /// * `plot_ui` - The plot UI to paint on.
 /// * `plot` - A tuple containing [`PlotData`] and [`PlotType`].
+/// * `secondary_y_transform` - Applied to plots bound to the secondary y-axis.
 /// * `axis_config` - For axis customization.
 /// * `line_width` - The width of plot lines.
 /// * `plot_settings` - Controls which plots to display.
+/// * `reset_zoom` - Whether to reset the bounds of the plot to fit its data.
+/// * `scroll_legend` - If set, the plots it hides are not painted and the plot it is hovering is highlighted.
+/// * `series_subset` - If set, only the plots with these names are painted.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "They are needed. Maybe a refactor could group some of them."
+)]
 fn fill_plot(
     plot_ui: &mut egui_plot::PlotUi,
     plot: (&mut PlotData, PlotType),
+    secondary_y_transform: Option<YTransform>,
     axis_config: &mut AxisConfig,
     line_width: f32,
     plot_settings: &PlotSettings,
+    reset_zoom: bool,
+    scroll_legend: Option<&ScrollLegend>,
+    series_subset: Option<&[String]>,
 ) {
     let (plot_data, plot_type) = plot;
+    if let Some(legend) = scroll_legend {
+        for pv in plot_data.plots_as_mut() {
+            if legend.is_hovered(&plot_settings.series_label(pv)) {
+                *pv.get_highlight_mut() = true;
+            }
+        }
+    }
 
     plot_util::plot_lines(
         plot_ui,
-        plot_settings.apply_filters(plot_data.plots()),
+        shown_plots(plot_settings, plot_data, series_subset).filter(|pv| {
+            scroll_legend.is_none_or(|legend| !legend.is_hidden(&plot_settings.series_label(pv)))
+        }),
         line_width,
         plot_settings.mipmap_cfg(),
         plot_ui.ctx().used_size().x as usize,
+        series_y_transform(axis_config, plot_settings, plot_type, secondary_y_transform),
+        |pv| plot_settings.series_label(pv),
     );
 
     plot_util::plot_labels(plot_ui, plot_data, &plot_settings.log_id_filter());
 
-    axis_config.handle_y_axis_lock(plot_ui, plot_type, |_| {});
+    // Auto bounds is requested between the y-axis lock handling, so a locked y-axis is enforced again on the next frame
+    if let Some(time_marker) = axis_config.time_marker() {
+        plot_ui.vline(VLine::new(time_marker).color(Color32::YELLOW));
+    }
+
+    let [x_min, _] = plot_ui.plot_bounds().min();
+    let [x_max, _] = plot_ui.plot_bounds().max();
+    axis_config.set_visible_x_range(x_min, x_max);
+
+    let zoom_x = axis_config.zoom_x_requested();
+    let center_x = axis_config.center_x_requested();
+    axis_config.handle_y_axis_lock(plot_ui, plot_type, |plot_ui| {
+        if reset_zoom {
+            plot_ui.set_auto_bounds(Vec2b::TRUE);
+        } else if let Some((x_min, x_max)) = zoom_x {
+            let mut bounds = plot_ui.plot_bounds();
+            bounds.set_x(&PlotBounds::from_min_max([x_min, 0.0], [x_max, 0.0]));
+            plot_ui.set_plot_bounds(bounds);
+        } else if let Some(x) = center_x {
+            let mut bounds = plot_ui.plot_bounds();
+            bounds.set_x_center_width(x, bounds.width());
+            plot_ui.set_plot_bounds(bounds);
+        }
+    });
+}
+
+/// Returns the plots in `plot_data` that should be painted according to the `plot_settings` and the `series_subset` (if any).
+///
+/// The plots are returned in the order they should be painted.
+fn shown_plots<'pv>(
+    plot_settings: &'pv PlotSettings,
+    plot_data: &'pv PlotData,
+    series_subset: Option<&'pv [String]>,
+) -> impl Iterator<Item = &'pv PlotValues> {
+    let mut plots: Vec<&PlotValues> = plot_settings
+        .apply_filters(plot_data.plots())
+        .filter(|pv| series_subset.is_none_or(|names| names.iter().any(|n| n == pv.name())))
+        .collect();
+    plot_settings.sort_by_draw_order(&mut plots);
+    plots.into_iter()
+}
+
+/// Returns the label and color of the shown plots in `plot_data`, as they are listed in the legend
+fn legend_entries(
+    plot_settings: &PlotSettings,
+    plot_data: &PlotData,
+    series_subset: Option<&[String]>,
+) -> Vec<(String, Color32)> {
+    shown_plots(plot_settings, plot_data, series_subset)
+        .map(|pv| (plot_settings.series_label(pv).into_owned(), pv.get_color()))
+        .collect()
+}
+
+/// Calculates the transform of the plots on the secondary y-axis in a plot area.
+fn secondary_y_transform(
+    axis_config: &AxisConfig,
+    plot_settings: &PlotSettings,
+    plot_data: &PlotData,
+    plot_type: PlotType,
+    series_subset: Option<&[String]>,
+) -> Option<YTransform> {
+    axis_config.secondary_y_axis().transform(
+        shown_plots(plot_settings, plot_data, series_subset),
+        |pv| {
+            plot_settings
+                .unit_conversion(pv.name())
+                .map(|c| c.scale_offset())
+        },
+        axis_config.log_y_axis().scale(plot_type),
+    )
+}
+
+/// Returns the unit of the plots on the primary y-axis in a plot area if they all have the same unit.
+fn primary_y_axis_unit(
+    axis_config: &AxisConfig,
+    plot_settings: &PlotSettings,
+    plot_data: &PlotData,
+    series_subset: Option<&[String]>,
+) -> Option<String> {
+    let mut units = shown_plots(plot_settings, plot_data, series_subset)
+        .filter(|pv| !axis_config.secondary_y_axis().contains(pv.name()))
+        .map(|pv| plot_settings.unit(pv));
+    let unit = units.next()??;
+    units.all(|u| u == Some(unit)).then(|| unit.to_owned())
+}
+
+/// Maps a y-value on the primary y-axis of a plot area back to the value it shows, i.e. undoes the log10 scale of the axis (if any).
+fn primary_y_value(axis_config: &AxisConfig, plot_type: PlotType, y: f64) -> f64 {
+    axis_config
+        .log_y_axis()
+        .scale(plot_type)
+        .map_or(y, |non_positive| YTransform::log10(non_positive).inverse(y))
+}
+
+/// Returns a function that gives the [`YTransform`] (if any) that should be applied to a series when it is painted.
+fn series_y_transform<'a>(
+    axis_config: &'a AxisConfig,
+    plot_settings: &'a PlotSettings,
+    plot_type: PlotType,
+    secondary_y_transform: Option<YTransform>,
+) -> impl Fn(&PlotValues) -> Option<YTransform> + 'a {
+    let primary_y_transform = axis_config
+        .log_y_axis()
+        .scale(plot_type)
+        .map(YTransform::log10);
+    move |pv| {
+        let y_transform = match secondary_y_transform {
+            Some(t) if axis_config.secondary_y_axis().contains(pv.name()) => Some(t),
+            _ => primary_y_transform,
+        };
+        match plot_settings.unit_conversion(pv.name()) {
+            Some(conversion) => {
+                let (scale, offset) = conversion.scale_offset();
+                Some(
+                    y_transform
+                        .unwrap_or(YTransform::IDENTITY)
+                        .with_unit_conversion(scale, offset),
+                )
+            }
+            None => y_transform,
+        }
+    }
+}
+
+/// Finds the sample that is closest to `pointer` on the screen among the shown plots in `plot_data`.
+///
+/// Returns the sample as it is painted, its y-value in the unit it is converted to (if any) and the plot it belongs to.
+fn closest_sample<'pv>(
+    plot_ui: &egui_plot::PlotUi,
+    plot_data: &'pv PlotData,
+    pointer: PlotPoint,
+    y_transform: impl Fn(&PlotValues) -> Option<YTransform>,
+    plot_settings: &'pv PlotSettings,
+    series_subset: Option<&'pv [String]>,
+) -> Option<(PlotPoint, f64, &'pv PlotValues)> {
+    let dpos_dvalue = plot_ui.transform().dpos_dvalue();
+    shown_plots(plot_settings, plot_data, series_subset)
+        .filter_map(|pv| {
+            plot_util::find_closest_point(
+                pv.raw_plot(),
+                [pointer.x, pointer.y],
+                dpos_dvalue,
+                y_transform(pv),
+            )
+            .map(|closest| (closest, pv))
+        })
+        .min_by(|(a, _), (b, _)| a.dist_sq.total_cmp(&b.dist_sq))
+        .map(|(closest, pv)| {
+            let [x, y] = closest.painted;
+            (PlotPoint::new(x, y), closest.value_y, pv)
+        })
 }
 
 /// Builds and configures a Plot UI (layout) with the specified settings.
@@ -169,33 +561,96 @@ fn fill_plot(
 /// # Arguments
 ///
 /// * `name` - The name of the plot.
+/// * `plot_type` - The plot area the plot is for.
 /// * `plot_height` - The height of the plot.
-/// * `legend_cfg` - The legend configuration.
 /// * `axis_config` - For axis customization.
 /// * `x_axes` - A vector of [`AxisHints`] for x-axis configuration.
 /// * `link_group` - An [`egui::Id`] for linking plots.
+/// * `secondary_y_transform` - If set, a secondary y-axis is added to the left that shows the values of the plots bound to it.
+/// * `y_unit` - If set, the primary y-axis is labeled with the unit.
 ///
 /// # Returns
 ///
 /// A configured [`Plot`] instance.
+#[allow(
+    clippy::too_many_arguments,
+    reason = "They are needed. Maybe a refactor could group some of them."
+)]
 fn build_plot_ui<'a>(
     name: &str,
+    plot_type: PlotType,
     plot_height: f32,
-    legend_cfg: Legend,
     axis_config: &AxisConfig,
     x_axes: Vec<AxisHints<'a>>,
     link_group: egui::Id,
+    secondary_y_transform: Option<YTransform>,
+    y_unit: Option<String>,
 ) -> Plot<'a> {
-    Plot::new(name)
-        .legend(legend_cfg)
+    let log_y = axis_config.log_y_axis().scale(plot_type);
+    let mut primary_y_axis = AxisHints::new_y().placement(HPlacement::Right);
+    if let Some(unit) = y_unit {
+        primary_y_axis = primary_y_axis.label(unit);
+    }
+    let mut y_axes = vec![match log_y {
+        Some(_) => primary_y_axis.formatter(format_log10_tick),
+        None if plot_type == PlotType::Percentage => {
+            primary_y_axis.formatter(|y, _range| format!("{:.0}%", y.value * 100.0))
+        }
+        None => primary_y_axis,
+    }];
+    if let Some(t) = secondary_y_transform {
+        y_axes.push(
+            AxisHints::new_y()
+                .placement(HPlacement::Left)
+                .formatter(move |mark, _range| format_secondary_y_tick(mark, t)),
+        );
+    }
+    let secondary_y_axis = axis_config.secondary_y_axis().clone();
+    let primary_y_transform = log_y.map(YTransform::log10);
+    let time_zone = axis_config.time_zone();
+
+    let plot = Plot::new(name)
         .height(plot_height)
         .show_axes(axis_config.show_axes())
         .show_grid(axis_config.show_grid())
-        .y_axis_position(HPlacement::Right)
-        .include_y(0.0)
-        .custom_x_axes(x_axes)
-        .label_formatter(crate::util::format_label_ns)
-        .link_axis(link_group, Vec2b::new(axis_config.link_x(), false))
+        .custom_y_axes(y_axes);
+    // Including 0 on a logarithmic axis would mean including 1 which is often orders of magnitude away from the data
+    let plot = match log_y {
+        Some(_) => plot,
+        None if plot_type == PlotType::Percentage => plot.include_y(0.0).include_y(1.0),
+        None => plot.include_y(0.0),
+    };
+    plot.custom_x_axes(x_axes)
+        .label_formatter(move |plot_name, val| {
+            let y_transform = match secondary_y_transform {
+                Some(t) if secondary_y_axis.contains_label(plot_name) => Some(t),
+                _ => primary_y_transform,
+            };
+            match y_transform {
+                Some(t) => crate::util::format_label_ns(
+                    plot_name,
+                    &PlotPoint::new(val.x, t.inverse(val.y)),
+                    time_zone,
+                ),
+                None => crate::util::format_label_ns(plot_name, val, time_zone),
+            }
+        })
+        .link_axis(link_group, axis_config.link_mode().linked_axes())
         .link_cursor(link_group, [axis_config.link_cursor_x(), false].into())
         .y_axis_min_width(50.0) // Adds enough margin for 5-digits
 }
+
+/// Formats a tick on the secondary y-axis with the value it has before being transformed onto the primary y-axis
+fn format_secondary_y_tick(mark: GridMark, transform: YTransform) -> String {
+    let value = transform.inverse(mark.value);
+    if transform.is_log10() {
+        return format_log10_value(value.log10());
+    }
+    let step = (transform.inverse(mark.step_size) - transform.inverse(0.0)).abs();
+    let decimals = if step > 0.0 {
+        (-step.log10()).ceil().clamp(0.0, 6.0) as usize
+    } else {
+        0
+    };
+    format!("{value:.decimals$}")
+}
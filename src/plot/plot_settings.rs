@@ -1,23 +1,42 @@
+use chrono::{DateTime, Utc};
 use date_settings::LoadedLogSettings;
+use draw_order::DrawOrder;
 use egui::{Color32, Key, Response, RichText};
+use egui_notify::Toasts;
 use egui_phosphor::regular;
 use mipmap_settings::MipMapSettings;
+use plot_areas::PlotAreas;
 use plot_filter::{PlotNameFilter, PlotNameShow};
 use plot_util::{MipMapConfiguration, PlotValues, Plots};
 use plot_visibility_config::PlotVisibilityConfig;
 use serde::{Deserialize, Serialize};
+use std::{borrow::Cow, path::PathBuf, time::Duration};
+use unit_conversion::{UnitConversion, UnitConversions};
+use visibility_presets::VisibilityPresets;
+
+use crate::app::supported_formats::logs::parse_info::ParseInfo;
+
+use super::PlotType;
 
 pub mod date_settings;
+mod draw_order;
 mod loaded_logs;
 pub mod mipmap_settings;
+mod plot_areas;
 mod plot_filter;
 mod plot_visibility_config;
+mod unit_conversion;
+mod visibility_presets;
 
 #[derive(PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 struct PlotSettingsUi {
     show_loaded_logs: bool,
     show_filter_settings: bool,
     filter_settings_text: String,
+    show_unit_settings: bool,
+    show_draw_order_settings: bool,
+    show_plot_area_settings: bool,
 }
 
 impl Default for PlotSettingsUi {
@@ -26,6 +45,9 @@ impl Default for PlotSettingsUi {
             show_loaded_logs: Default::default(),
             show_filter_settings: Default::default(),
             filter_settings_text: format!("{} Filter", regular::FUNNEL),
+            show_unit_settings: Default::default(),
+            show_draw_order_settings: Default::default(),
+            show_plot_area_settings: Default::default(),
         }
     }
 }
@@ -42,6 +64,7 @@ impl PlotSettingsUi {
 }
 
 #[derive(Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
 pub struct PlotSettings {
     // The ID to assign to the next loaded log
     next_log_id: u16,
@@ -54,10 +77,17 @@ pub struct PlotSettings {
     display_plot_count: u8,
     // Plot names and whether or not they should be shown (painted)
     plot_name_filter: PlotNameFilter,
+    visibility_presets: VisibilityPresets,
+    unit_conversions: UnitConversions,
+    draw_order: DrawOrder,
+    plot_areas: PlotAreas,
     ps_ui: PlotSettingsUi,
     loaded_log_settings: Vec<LoadedLogSettings>,
     mipmap_settings: MipMapSettings,
     apply_deletions: bool,
+    // Set for the frame after the concatenate button is clicked, not persisted so the logs aren't concatenated when the app is restarted
+    #[serde(skip)]
+    concatenate_logs: bool,
 }
 
 impl PlotSettings {
@@ -67,6 +97,9 @@ impl PlotSettings {
         } else {
             self.show_loaded_files(ui);
             self.ui_plot_filter_settings(ui);
+            self.ui_unit_settings(ui);
+            self.ui_draw_order_settings(ui);
+            self.ui_plot_area_settings(ui);
             self.mipmap_settings.show(ui);
         }
         self.visibility.toggle_visibility_ui(ui);
@@ -78,6 +111,8 @@ impl PlotSettings {
             egui::Window::new(self.ps_ui.filter_settings_text())
                 .open(&mut self.ps_ui.show_filter_settings)
                 .show(ui.ctx(), |ui| {
+                    self.visibility_presets.show(ui, &mut self.plot_name_filter);
+                    ui.separator();
                     self.plot_name_filter.show(ui);
                 });
             if ui.ctx().input(|i| i.key_pressed(Key::Escape)) {
@@ -86,6 +121,72 @@ impl PlotSettings {
         }
     }
 
+    fn ui_unit_settings(&mut self, ui: &mut egui::Ui) {
+        let unit_settings_text = format!("{} Units", regular::RULER);
+        ui.toggle_value(
+            &mut self.ps_ui.show_unit_settings,
+            unit_settings_text.clone(),
+        );
+        if self.ps_ui.show_unit_settings {
+            let plot_names: Vec<&str> = self.plot_name_filter.plot_names().collect();
+            egui::Window::new(unit_settings_text)
+                .open(&mut self.ps_ui.show_unit_settings)
+                .show(ui.ctx(), |ui| {
+                    ui.label("Convert the values of a plot to another unit when it is painted");
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        self.unit_conversions.show(ui, &plot_names);
+                    });
+                });
+            if ui.ctx().input(|i| i.key_pressed(Key::Escape)) {
+                self.ps_ui.show_unit_settings = false;
+            }
+        }
+    }
+
+    fn ui_draw_order_settings(&mut self, ui: &mut egui::Ui) {
+        let draw_order_text = format!("{} Draw order", regular::STACK);
+        ui.toggle_value(
+            &mut self.ps_ui.show_draw_order_settings,
+            draw_order_text.clone(),
+        );
+        if self.ps_ui.show_draw_order_settings {
+            let plot_names: Vec<&str> = self.plot_name_filter.plot_names().collect();
+            egui::Window::new(draw_order_text)
+                .open(&mut self.ps_ui.show_draw_order_settings)
+                .show(ui.ctx(), |ui| {
+                    ui.label("Plots at the top are painted on top of the plots below them");
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        self.draw_order.show(ui, &plot_names);
+                    });
+                });
+            if ui.ctx().input(|i| i.key_pressed(Key::Escape)) {
+                self.ps_ui.show_draw_order_settings = false;
+            }
+        }
+    }
+
+    fn ui_plot_area_settings(&mut self, ui: &mut egui::Ui) {
+        let plot_area_text = format!("{} Plot areas", regular::ROWS);
+        ui.toggle_value(
+            &mut self.ps_ui.show_plot_area_settings,
+            plot_area_text.clone(),
+        );
+        if self.ps_ui.show_plot_area_settings {
+            let plot_names: Vec<&str> = self.plot_name_filter.plot_names().collect();
+            egui::Window::new(plot_area_text)
+                .open(&mut self.ps_ui.show_plot_area_settings)
+                .show(ui.ctx(), |ui| {
+                    ui.label("Move plots to another plot area if their values don't fit the one they were placed in");
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        self.plot_areas.show(ui, &plot_names);
+                    });
+                });
+            if ui.ctx().input(|i| i.key_pressed(Key::Escape)) {
+                self.ps_ui.show_plot_area_settings = false;
+            }
+        }
+    }
+
     fn ui_show_or_hide_all_buttons(ui: &mut egui::Ui, loaded_files: &mut [LoadedLogSettings]) {
         let mut hide_all = false;
         let mut show_all = false;
@@ -114,6 +215,11 @@ impl PlotSettings {
     }
 
     fn show_loaded_files(&mut self, ui: &mut egui::Ui) {
+        // Reset the state of the cursor hovering on a log. It is set again while showing the loaded files window,
+        // so if the window is closed or the pointer left the log, the highlight of its plots is cleared.
+        for settings in &mut self.loaded_log_settings {
+            *settings.cursor_hovering_on_mut() = false;
+        }
         let loaded_files_count = self.loaded_log_settings.len();
         let visibility_icon = if self.ps_ui.show_loaded_logs {
             regular::EYE
@@ -140,6 +246,13 @@ impl PlotSettings {
                     egui::ScrollArea::vertical().show(ui, |ui| {
                         ui.horizontal_wrapped(|ui| {
                             Self::ui_show_or_hide_all_buttons(ui, &mut self.loaded_log_settings);
+                            if ui
+                                .button(RichText::new("Concatenate").strong().heading())
+                                .on_hover_text("Concatenate the shown logs of the same type into one log, e.g. a recording that is split across several files")
+                                .clicked()
+                            {
+                                self.concatenate_logs = true;
+                            }
                         });
                         egui::Grid::new("log_settings_grid").show(ui, |ui| {
                             ui.label("");
@@ -174,11 +287,21 @@ impl PlotSettings {
 
     /// Needs to be called once (and only once!) per frame before querying for plot ui settings, such as
     /// how many plots to paint and more.
-    pub fn refresh(&mut self, plots: &mut Plots) {
+    ///
+    /// The user is notified through the `toasts` if anything unexpected happens while applying the settings.
+    pub fn refresh(&mut self, plots: &mut Plots, toasts: &mut Toasts) {
         if self.apply_deletions {
             self.remove_if_marked_for_deletion(plots);
             self.apply_deletions = false;
         }
+        if self.concatenate_logs {
+            self.concatenate_shown_logs(plots, toasts);
+            self.concatenate_logs = false;
+        }
+        self.plot_areas.add_missing_plots(plots);
+        if self.plot_areas.move_plots(plots) {
+            self.invalidate_plot = true;
+        }
         self.set_highlighted(plots);
         self.update_plot_dates(plots);
         self.calc_plot_display_settings(plots);
@@ -237,6 +360,41 @@ impl PlotSettings {
         }
     }
 
+    /// Returns the plot area a new plot should be added to, which is `default_area` unless the user moved plots with that name to another area
+    pub fn plot_area_for_new_plot(&mut self, plot_name: &str, default_area: PlotType) -> PlotType {
+        self.plot_areas.area_for_new_plot(plot_name, default_area)
+    }
+
+    /// Returns the names of all loaded plots in alphabetical order
+    pub fn plot_names(&self) -> Vec<&str> {
+        self.plot_name_filter.plot_names().collect()
+    }
+
+    /// Returns the unit conversion of the plot with the given name if any
+    pub fn unit_conversion(&self, plot_name: &str) -> Option<&UnitConversion> {
+        self.unit_conversions.get(plot_name)
+    }
+
+    /// Returns the unit of a plot, the unit it is converted to if any, otherwise the unit it was parsed with if any
+    pub fn unit<'a>(&'a self, plot_vals: &'a PlotValues) -> Option<&'a str> {
+        self.unit_conversion(plot_vals.name())
+            .map(UnitConversion::unit)
+            .or_else(|| plot_vals.get_unit())
+    }
+
+    /// Returns the label to show for a plot, with the unit appended if the plot has one
+    pub fn series_label<'pv>(&self, plot_vals: &'pv PlotValues) -> Cow<'pv, str> {
+        match self.unit(plot_vals) {
+            Some(unit) => Cow::Owned(format!("{} [{unit}]", plot_vals.label())),
+            None => Cow::Borrowed(plot_vals.label()),
+        }
+    }
+
+    /// Sorts `plots` in the order they should be painted, such that the last plot is painted on top
+    pub fn sort_by_draw_order(&self, plots: &mut [&PlotValues]) {
+        plots.sort_by_key(|pv| self.draw_order.sort_key(pv.name()));
+    }
+
     pub fn apply_filters<'pv>(
         &'pv self,
         plot_vals: &'pv [PlotValues],
@@ -263,6 +421,39 @@ impl PlotSettings {
         self.loaded_log_settings.push(log_settings);
     }
 
+    /// Returns the IDs and source paths of the logs the user requested to be reloaded from disk since the last call
+    pub fn take_reload_requests(&mut self) -> Vec<(u16, PathBuf)> {
+        self.loaded_log_settings
+            .iter_mut()
+            .filter_map(|settings| {
+                if !settings.take_reload_request() {
+                    return None;
+                }
+                settings
+                    .source_path()
+                    .map(|path| (settings.log_id(), path.to_path_buf()))
+            })
+            .collect()
+    }
+
+    /// Updates the settings of the log with `log_id` after its plots have been replaced by those of the reloaded log
+    pub fn log_reloaded(
+        &mut self,
+        log_id: u16,
+        start_date: DateTime<Utc>,
+        log_metadata: Option<Vec<(String, String)>>,
+        parse_info: Option<ParseInfo>,
+    ) {
+        if let Some(settings) = self
+            .loaded_log_settings
+            .iter_mut()
+            .find(|settings| settings.log_id() == log_id)
+        {
+            settings.reloaded(start_date, log_metadata, parse_info);
+        }
+        self.invalidate_plot = true;
+    }
+
     // The id filter specifies which plots belonging to which logs should not be painted on the plot ui.
     pub fn log_id_filter(&self) -> Vec<u16> {
         let mut log_id_filter: Vec<u16> = vec![];
@@ -351,6 +542,66 @@ impl PlotSettings {
         self.invalidate_plot = true;
     }
 
+    // Concatenates the shown logs with the same descriptive name into the one with the earliest start date
+    fn concatenate_shown_logs(&mut self, plots: &mut Plots, toasts: &mut Toasts) {
+        let mut shown_logs: Vec<&LoadedLogSettings> = self
+            .loaded_log_settings
+            .iter()
+            .filter(|settings| settings.show_log())
+            .collect();
+        shown_logs.sort_by_key(|settings| settings.start_date());
+        // The log ID to concatenate onto and the IDs of the logs to concatenate onto it, by descriptive name
+        let mut groups: Vec<(&str, u16, Vec<u16>)> = Vec::new();
+        for settings in shown_logs {
+            if let Some((_, _, other_log_ids)) = groups
+                .iter_mut()
+                .find(|(name, ..)| *name == settings.descriptive_name())
+            {
+                other_log_ids.push(settings.log_id());
+            } else {
+                groups.push((settings.descriptive_name(), settings.log_id(), Vec::new()));
+            }
+        }
+        let groups: Vec<(u16, Vec<u16>)> = groups
+            .into_iter()
+            .filter(|(_, _, other_log_ids)| !other_log_ids.is_empty())
+            .map(|(_, log_id, other_log_ids)| (log_id, other_log_ids))
+            .collect();
+        if groups.is_empty() {
+            return;
+        }
+
+        for (log_id, other_log_ids) in &groups {
+            let dropped_points = plots
+                .percentage_mut()
+                .concatenate_logs(*log_id, other_log_ids)
+                + plots
+                    .one_to_hundred_mut()
+                    .concatenate_logs(*log_id, other_log_ids)
+                + plots
+                    .thousands_mut()
+                    .concatenate_logs(*log_id, other_log_ids);
+            if dropped_points > 0 {
+                let msg = format!("The concatenated logs overlap in time, dropped {dropped_points} points at timestamps that already had a point");
+                log::warn!("{msg}");
+                toasts.warning(msg).duration(Some(Duration::from_secs(10)));
+            }
+            if let Some(settings) = self
+                .loaded_log_settings
+                .iter_mut()
+                .find(|settings| settings.log_id() == *log_id)
+            {
+                settings.add_concatenated_logs(other_log_ids.len());
+            }
+        }
+        self.loaded_log_settings.retain(|settings| {
+            !groups
+                .iter()
+                .any(|(_, other_log_ids)| other_log_ids.contains(&settings.log_id()))
+        });
+        self.invalidate_plot = true;
+    }
+
     /// Returns true if changes in plot settings occurred such that various cached values
     /// related to plot layout needs to be recalculated.
     pub fn cached_plots_invalidated(&self) -> bool {
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use chrono::{DateTime, NaiveDateTime, Utc};
 use egui::RichText;
 use plot_util::{PlotData, Plots};
@@ -58,6 +60,19 @@ pub struct LoadedLogSettings {
     parse_info: Option<ParseInfo>,
     marked_for_deletion: bool,
     is_hovered: bool,
+    // How many logs have been concatenated into this one (including itself)
+    #[serde(default = "default_concatenated_log_count")]
+    concatenated_log_count: usize,
+    // The file the log was parsed from, if it can be parsed again from disk
+    #[serde(default)]
+    source_path: Option<PathBuf>,
+    #[serde(skip)]
+    reload_requested: bool,
+}
+
+// The state of older versions is from before logs could be concatenated, so each log is a single log
+fn default_concatenated_log_count() -> usize {
+    1
 }
 
 impl LoadedLogSettings {
@@ -67,6 +82,7 @@ impl LoadedLogSettings {
         start_date: DateTime<Utc>,
         log_metadata: Option<Vec<(String, String)>>,
         parse_info: Option<ParseInfo>,
+        source_path: Option<PathBuf>,
     ) -> Self {
         let log_metadata = log_metadata.map(|l| {
             l.into_iter()
@@ -88,9 +104,34 @@ impl LoadedLogSettings {
             parse_info,
             marked_for_deletion: false,
             is_hovered: false,
+            concatenated_log_count: 1,
+            source_path,
+            reload_requested: false,
         }
     }
 
+    /// Updates the settings with the info of the log after it was parsed again from its source path.
+    ///
+    /// The offset of the start date from the original start date is kept, `date_changed` is set if the plots of the log need to be offset.
+    pub fn reloaded(
+        &mut self,
+        start_date: DateTime<Utc>,
+        log_metadata: Option<Vec<(String, String)>>,
+        parse_info: Option<ParseInfo>,
+    ) {
+        let offset = self.start_date - self.original_start_date;
+        self.original_start_date = start_date;
+        self.start_date = start_date + offset;
+        self.tmp_date_buf.clear();
+        self.date_changed = offset != chrono::TimeDelta::zero();
+        self.log_metadata = log_metadata.map(|l| {
+            l.into_iter()
+                .map(|l| LoadedLogMetadata::new(l.0, l.1))
+                .collect()
+        });
+        self.parse_info = parse_info;
+    }
+
     pub fn start_date(&self) -> DateTime<Utc> {
         self.start_date
     }
@@ -100,12 +141,26 @@ impl LoadedLogSettings {
     }
 
     pub fn log_label(&self) -> String {
-        format!(
+        let label = format!(
             "#{log_id} {descriptive_name}",
             log_id = self.log_id,
             descriptive_name = self.log_descriptive_name,
             //start_date = self.start_date.naive_utc()
-        )
+        );
+        if self.concatenated_log_count > 1 {
+            format!("{label} ({} logs)", self.concatenated_log_count)
+        } else {
+            label
+        }
+    }
+
+    pub fn descriptive_name(&self) -> &str {
+        &self.log_descriptive_name
+    }
+
+    /// Registers that `log_count` other logs have been concatenated into this one
+    pub fn add_concatenated_logs(&mut self, log_count: usize) {
+        self.concatenated_log_count += log_count;
     }
 
     /// This is the ID that connects settings to plots
@@ -129,6 +184,23 @@ impl LoadedLogSettings {
         self.parse_info
     }
 
+    /// The file the log was parsed from, [`None`] if it wasn't parsed from a file on disk or it is a concatenation of several logs
+    pub fn source_path(&self) -> Option<&Path> {
+        if self.concatenated_log_count > 1 {
+            return None;
+        }
+        self.source_path.as_deref()
+    }
+
+    /// Returns true if the user requested the log to be reloaded since the last call
+    pub fn take_reload_request(&mut self) -> bool {
+        std::mem::take(&mut self.reload_requested)
+    }
+
+    pub fn request_reload(&mut self) {
+        self.reload_requested = true;
+    }
+
     pub fn marked_for_deletion(&self) -> bool {
         self.marked_for_deletion
     }
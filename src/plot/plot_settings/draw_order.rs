@@ -0,0 +1,100 @@
+use egui::RichText;
+use egui_phosphor::regular;
+use serde::{Deserialize, Serialize};
+
+/// The order that plots (by name) are painted in, the first plot is painted on top of the others.
+///
+/// Plots that have not been ordered by the user are painted below the ordered ones.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct DrawOrder {
+    plot_names: Vec<String>,
+}
+
+impl DrawOrder {
+    /// Returns the key to sort plots by before painting them, plots with a higher key are painted on top.
+    pub fn sort_key(&self, plot_name: &str) -> usize {
+        self.plot_names
+            .iter()
+            .position(|n| n == plot_name)
+            .map_or(0, |idx| self.plot_names.len() - idx)
+    }
+
+    /// Returns `plot_names` in the order they are drawn, from top to bottom
+    fn ordered<'n>(&self, plot_names: &[&'n str]) -> Vec<&'n str> {
+        let mut ordered = plot_names.to_vec();
+        // Stable so the plots that have not been ordered keep their order
+        ordered.sort_by_key(|name| std::cmp::Reverse(self.sort_key(name)));
+        ordered
+    }
+
+    /// Moves the plot at `idx` of the `ordered` plots to `new_idx`
+    fn move_plot(&mut self, ordered: &[&str], idx: usize, new_idx: usize) {
+        // Keep the order of plots that are not loaded anymore, they might be loaded again later
+        let not_loaded = self
+            .plot_names
+            .iter()
+            .filter(|n| !ordered.contains(&n.as_str()))
+            .cloned();
+        let mut plot_names: Vec<String> = ordered.iter().map(|n| (*n).to_owned()).collect();
+        let name = plot_names.remove(idx);
+        plot_names.insert(new_idx, name);
+        plot_names.extend(not_loaded);
+        self.plot_names = plot_names;
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, plot_names: &[&str]) {
+        let ordered = self.ordered(plot_names);
+        let mut move_plot = None;
+        egui::Grid::new("draw_order").show(ui, |ui| {
+            for (idx, name) in ordered.iter().enumerate() {
+                if ui
+                    .add_enabled(idx > 0, egui::Button::new(regular::ARROW_UP))
+                    .on_hover_text("Paint on top of the plot above")
+                    .clicked()
+                {
+                    move_plot = Some((idx, idx - 1));
+                }
+                if ui
+                    .add_enabled(
+                        idx + 1 < ordered.len(),
+                        egui::Button::new(regular::ARROW_DOWN),
+                    )
+                    .on_hover_text("Paint below the plot below")
+                    .clicked()
+                {
+                    move_plot = Some((idx, idx + 1));
+                }
+                ui.label(RichText::new(*name).strong());
+                ui.end_row();
+            }
+        });
+        if let Some((idx, new_idx)) = move_plot {
+            self.move_plot(&ordered, idx, new_idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unordered_plots_keep_their_order() {
+        let draw_order = DrawOrder::default();
+        assert_eq!(draw_order.ordered(&["RPM", "Current"]), ["RPM", "Current"]);
+        assert_eq!(draw_order.sort_key("RPM"), draw_order.sort_key("Current"));
+    }
+
+    #[test]
+    fn test_move_plot_to_top() {
+        let mut draw_order = DrawOrder::default();
+        draw_order.move_plot(&["RPM", "Current", "Voltage"], 2, 0);
+        assert_eq!(
+            draw_order.ordered(&["RPM", "Current", "Voltage"]),
+            ["Voltage", "RPM", "Current"]
+        );
+        assert!(draw_order.sort_key("Voltage") > draw_order.sort_key("RPM"));
+        // Plots that were not loaded when ordering are painted below the ordered ones
+        assert_eq!(draw_order.sort_key("Temperature"), 0);
+    }
+}
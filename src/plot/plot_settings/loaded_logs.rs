@@ -9,11 +9,9 @@ use crate::{
 
 use super::date_settings::LoadedLogSettings;
 
+/// Shows the settings line of a loaded log. The hover state of the log is set to true if we detect hover on any of the elements on the line,
+/// it is expected to have been reset before this is called.
 pub fn log_date_settings_ui(ui: &mut egui::Ui, loaded_log: &mut LoadedLogSettings) {
-    // Reset the state the the cursor is hovering on a log. We then set it to true if
-    // we detect hover on any of the elements on the line for the given log
-    *loaded_log.cursor_hovering_on_mut() = false;
-
     let log_name_date = loaded_log.log_label();
     let check_box_text = RichText::new(if loaded_log.show_log() {
         regular::EYE
@@ -35,6 +33,10 @@ pub fn log_date_settings_ui(ui: &mut egui::Ui, loaded_log: &mut LoadedLogSetting
     if ui_log_button.clicked() {
         loaded_log.toggle_clicked();
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Some(path) = loaded_log.source_path() {
+        ui_log_button.context_menu(|ui| open_containing_folder_button(ui, path));
+    }
     if ui_log_button.hovered() {
         ui_log_button.on_hover_text("Click to modify log settings");
         *loaded_log.cursor_hovering_on_mut() = true;
@@ -59,6 +61,25 @@ pub fn log_date_settings_ui(ui: &mut egui::Ui, loaded_log: &mut LoadedLogSetting
         *loaded_log.cursor_hovering_on_mut() = true;
     }
 
+    let reload_button_resp = ui
+        .add_enabled(
+            loaded_log.source_path().is_some(),
+            egui::Button::new(regular::ARROW_CLOCKWISE),
+        )
+        .on_disabled_hover_text(
+            "Only logs loaded from a file (not from a zip archive or concatenated) can be reloaded",
+        );
+    if reload_button_resp.clicked() {
+        loaded_log.request_reload();
+    }
+    if reload_button_resp.hovered() {
+        if let Some(path) = loaded_log.source_path() {
+            let hover_text = format!("Reload from disk: {}", path.display());
+            reload_button_resp.on_hover_text(hover_text);
+        }
+        *loaded_log.cursor_hovering_on_mut() = true;
+    }
+
     if loaded_log.tmp_date_buf.is_empty() {
         loaded_log.tmp_date_buf = loaded_log
             .start_date()
@@ -70,6 +91,48 @@ pub fn log_date_settings_ui(ui: &mut egui::Ui, loaded_log: &mut LoadedLogSetting
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
+fn open_containing_folder_button(ui: &mut egui::Ui, path: &std::path::Path) {
+    let exists = path.exists();
+    if ui
+        .add_enabled(
+            exists,
+            egui::Button::new(format!("{} Open containing folder", regular::FOLDER_OPEN)),
+        )
+        .on_disabled_hover_text("The file no longer exists, it may have been moved or deleted")
+        .clicked()
+    {
+        if let Err(e) = open_containing_folder(path) {
+            log::error!("Failed opening the folder of '{}': {e}", path.display());
+        }
+        ui.close_menu();
+    }
+}
+
+/// Opens the file explorer of the OS at the folder containing `path`, with the file selected where the OS supports it.
+#[cfg(not(target_arch = "wasm32"))]
+fn open_containing_folder(path: &std::path::Path) -> std::io::Result<()> {
+    use std::process::Command;
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("explorer");
+        // Explorer expects the path to be part of the same argument as `/select,`
+        let mut select_arg = std::ffi::OsString::from("/select,");
+        select_arg.push(path);
+        cmd.arg(select_arg);
+        cmd
+    } else if cfg!(target_os = "macos") {
+        let mut cmd = Command::new("open");
+        cmd.arg("-R").arg(path);
+        cmd
+    } else {
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(path.parent().unwrap_or(path));
+        cmd
+    };
+    // The explorer runs on its own so it isn't waited on
+    cmd.spawn().map(drop)
+}
+
 fn log_settings_window(ui: &egui::Ui, settings: &mut LoadedLogSettings, log_name_date: &str) {
     // State of window bound to the 'X'-button that closes the window
     let mut open = true;
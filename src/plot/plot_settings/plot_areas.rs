@@ -0,0 +1,228 @@
+use egui::RichText;
+use plot_util::{PlotData, Plots};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::plot::PlotType;
+
+const ALL_PLOT_TYPES: [PlotType; 3] = [
+    PlotType::Percentage,
+    PlotType::Hundreds,
+    PlotType::Thousands,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct PlotArea {
+    plot_name: String,
+    // The area given by the expected range of the plot when it was parsed
+    default_area: PlotType,
+    user_area: Option<PlotType>,
+}
+
+impl PlotArea {
+    fn area(&self) -> PlotType {
+        self.user_area.unwrap_or(self.default_area)
+    }
+}
+
+/// Keeps track of the plot area that each plot (by name) is painted in.
+///
+/// By default a plot is painted in the area that matches the expected range it was parsed with,
+/// but the user can move it to another area if that doesn't fit the data.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PlotAreas {
+    // Only the plots the user moved are persisted, the rest are added again when their plots are added or restored
+    #[serde(serialize_with = "serialize_user_areas")]
+    plot_areas: Vec<PlotArea>,
+    // Set when the user changes the area of a plot, until the plots are moved to their new area
+    #[serde(skip)]
+    changed: bool,
+}
+
+impl PlotAreas {
+    /// Returns the area the plot with the given name should be added to, `default_area` is the area given by its expected range.
+    pub fn area_for_new_plot(&mut self, plot_name: &str, default_area: PlotType) -> PlotType {
+        match self
+            .plot_areas
+            .binary_search_by(|a| a.plot_name.as_str().cmp(plot_name))
+        {
+            Ok(idx) => self.plot_areas[idx].area(),
+            Err(idx) => {
+                self.plot_areas.insert(
+                    idx,
+                    PlotArea {
+                        plot_name: plot_name.to_owned(),
+                        default_area,
+                        user_area: None,
+                    },
+                );
+                default_area
+            }
+        }
+    }
+
+    /// Adds the plots in `plots` that are not known yet in the area they are in, e.g. plots restored along with the state of the app.
+    pub fn add_missing_plots(&mut self, plots: &Plots) {
+        for plot_type in ALL_PLOT_TYPES {
+            for pv in plot_data(plots, plot_type).plots() {
+                self.area_for_new_plot(pv.name(), plot_type);
+            }
+        }
+    }
+
+    fn area(&self, plot_name: &str) -> Option<PlotType> {
+        self.plot_areas
+            .iter()
+            .find(|a| a.plot_name == plot_name)
+            .map(PlotArea::area)
+    }
+
+    /// Moves the plots in `plots` that are not in their area to it, if the area of any plot was changed since the last call.
+    ///
+    /// Returns true if any plots were moved.
+    pub fn move_plots(&mut self, plots: &mut Plots) -> bool {
+        if !std::mem::take(&mut self.changed) {
+            return false;
+        }
+        let mut moved = Vec::new();
+        for plot_type in ALL_PLOT_TYPES {
+            let plot_vals = plot_data_mut(plots, plot_type).plots_as_mut();
+            let (stay, to_move) = plot_vals
+                .drain(..)
+                .partition(|pv| self.area(pv.name()).is_none_or(|area| area == plot_type));
+            *plot_vals = stay;
+            moved.extend(to_move);
+        }
+        let any_moved = !moved.is_empty();
+        for pv in moved {
+            let area = self
+                .area(pv.name())
+                .expect("Only plots with a known area are moved");
+            plot_data_mut(plots, area).plots_as_mut().push(pv);
+        }
+        any_moved
+    }
+
+    /// Shows the area of each plot in `plot_names`, the names of the loaded plots
+    pub fn show(&mut self, ui: &mut egui::Ui, plot_names: &[&str]) {
+        egui::Grid::new("plot_areas").show(ui, |ui| {
+            for plot_area in self
+                .plot_areas
+                .iter_mut()
+                .filter(|a| plot_names.contains(&a.plot_name.as_str()))
+            {
+                ui.label(RichText::new(&plot_area.plot_name).strong());
+                let auto_text = format!("Auto ({})", plot_area.default_area);
+                let previous_area = plot_area.user_area;
+                egui::ComboBox::from_id_salt(&plot_area.plot_name)
+                    .selected_text(
+                        plot_area
+                            .user_area
+                            .map_or(auto_text.clone(), |area| area.to_string()),
+                    )
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut plot_area.user_area, None, auto_text);
+                        for plot_type in ALL_PLOT_TYPES {
+                            ui.selectable_value(
+                                &mut plot_area.user_area,
+                                Some(plot_type),
+                                plot_type.to_string(),
+                            );
+                        }
+                    });
+                if plot_area.user_area != previous_area {
+                    self.changed = true;
+                }
+                ui.end_row();
+            }
+        });
+    }
+}
+
+fn serialize_user_areas<S: Serializer>(
+    plot_areas: &[PlotArea],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(plot_areas.iter().filter(|a| a.user_area.is_some()))
+}
+
+fn plot_data(plots: &Plots, plot_type: PlotType) -> &PlotData {
+    match plot_type {
+        PlotType::Percentage => plots.percentage(),
+        PlotType::Hundreds => plots.one_to_hundred(),
+        PlotType::Thousands => plots.thousands(),
+    }
+}
+
+fn plot_data_mut(plots: &mut Plots, plot_type: PlotType) -> &mut PlotData {
+    match plot_type {
+        PlotType::Percentage => plots.percentage_mut(),
+        PlotType::Hundreds => plots.one_to_hundred_mut(),
+        PlotType::Thousands => plots.thousands_mut(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plot_util::PlotValues;
+
+    use super::*;
+
+    #[test]
+    fn test_new_plot_gets_user_area() {
+        let mut plot_areas = PlotAreas::default();
+        assert_eq!(
+            plot_areas.area_for_new_plot("RPM", PlotType::Thousands),
+            PlotType::Thousands
+        );
+        plot_areas.plot_areas[0].user_area = Some(PlotType::Hundreds);
+        assert_eq!(
+            plot_areas.area_for_new_plot("RPM", PlotType::Thousands),
+            PlotType::Hundreds
+        );
+    }
+
+    #[test]
+    fn test_move_plots() {
+        let mut plots = Plots::default();
+        plots.thousands_mut().plots_as_mut().push(PlotValues::new(
+            vec![[0.0, 1.0]],
+            "RPM".to_owned(),
+            1,
+        ));
+        let mut plot_areas = PlotAreas::default();
+        plot_areas.area_for_new_plot("RPM", PlotType::Thousands);
+        assert!(!plot_areas.move_plots(&mut plots));
+
+        plot_areas.plot_areas[0].user_area = Some(PlotType::Percentage);
+        plot_areas.changed = true;
+        assert!(plot_areas.move_plots(&mut plots));
+        assert!(plots.thousands().plots().is_empty());
+        assert_eq!(plots.percentage().plots()[0].name(), "RPM");
+    }
+
+    #[test]
+    fn test_only_moved_plots_are_persisted() -> testresult::TestResult {
+        let mut plot_areas = PlotAreas::default();
+        plot_areas.area_for_new_plot("RPM", PlotType::Thousands);
+        plot_areas.area_for_new_plot("Temp", PlotType::Hundreds);
+        plot_areas.plot_areas[0].user_area = Some(PlotType::Hundreds);
+
+        let restored: PlotAreas = serde_json::from_str(&serde_json::to_string(&plot_areas)?)?;
+        assert_eq!(restored.plot_areas.len(), 1);
+        assert_eq!(restored.area("RPM"), Some(PlotType::Hundreds));
+        Ok(())
+    }
+
+    #[test]
+    fn test_restored_plots_are_added() {
+        let mut plots = Plots::default();
+        plots
+            .one_to_hundred_mut()
+            .plots_as_mut()
+            .push(PlotValues::new(vec![[0.0, 1.0]], "Temp".to_owned(), 1));
+        let mut plot_areas = PlotAreas::default();
+        plot_areas.add_missing_plots(&plots);
+        assert_eq!(plot_areas.area("Temp"), Some(PlotType::Hundreds));
+        assert_eq!(plot_areas.plot_areas[0].default_area, PlotType::Hundreds);
+    }
+}
@@ -19,6 +19,24 @@ impl PlotNameFilter {
         self.plots.iter().any(|p| p.name() == plot_name)
     }
 
+    /// Returns the names of all plots in alphabetical order
+    pub fn plot_names(&self) -> impl Iterator<Item = &str> {
+        self.plots.iter().map(|p| p.name())
+    }
+
+    /// Returns the names of the plots that are shown in alphabetical order
+    pub fn shown_plot_names(&self) -> impl Iterator<Item = &str> {
+        self.plots.iter().filter(|p| p.show()).map(|p| p.name())
+    }
+
+    /// Shows the plots with the given names and hides all others
+    pub fn show_only(&mut self, plot_names: &[String]) {
+        for p in &mut self.plots {
+            let show = plot_names.iter().any(|name| name == p.name());
+            p.set_show(show);
+        }
+    }
+
     /// Takes in a slice of [`PlotValues`] and a function that filters based on log id
     /// and returns an iterator that yields all the [`PlotValues`] that should be shown
     ///
@@ -0,0 +1,165 @@
+use egui::RichText;
+use serde::{Deserialize, Serialize};
+
+/// A conversion of the y-values of a plot to another unit, applied when the plot is painted.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum UnitConversion {
+    CelsiusToFahrenheit,
+    SecondsToMinutes,
+    MinutesToHours,
+    HoursToMinutes,
+    Custom {
+        scale: f64,
+        offset: f64,
+        unit: String,
+    },
+}
+
+impl UnitConversion {
+    const PRESETS: [Self; 4] = [
+        Self::CelsiusToFahrenheit,
+        Self::SecondsToMinutes,
+        Self::MinutesToHours,
+        Self::HoursToMinutes,
+    ];
+
+    /// Returns the scale and offset that converts a value to the new unit as `value * scale + offset`
+    pub fn scale_offset(&self) -> (f64, f64) {
+        match self {
+            Self::CelsiusToFahrenheit => (1.8, 32.0),
+            Self::SecondsToMinutes | Self::MinutesToHours => (1.0 / 60.0, 0.0),
+            Self::HoursToMinutes => (60.0, 0.0),
+            Self::Custom { scale, offset, .. } => (*scale, *offset),
+        }
+    }
+
+    /// The unit that the values are converted to
+    pub fn unit(&self) -> &str {
+        match self {
+            Self::CelsiusToFahrenheit => "°F",
+            Self::SecondsToMinutes | Self::HoursToMinutes => "min",
+            Self::MinutesToHours => "h",
+            Self::Custom { unit, .. } => unit,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::CelsiusToFahrenheit => "°C → °F",
+            Self::SecondsToMinutes => "s → min",
+            Self::MinutesToHours => "min → h",
+            Self::HoursToMinutes => "h → min",
+            Self::Custom { .. } => "Custom",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+struct PlotUnitConversion {
+    plot_name: String,
+    conversion: UnitConversion,
+}
+
+/// The unit conversions that are applied to plots (by name)
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UnitConversions {
+    conversions: Vec<PlotUnitConversion>,
+}
+
+impl UnitConversions {
+    pub fn get(&self, plot_name: &str) -> Option<&UnitConversion> {
+        self.conversions
+            .iter()
+            .find(|c| c.plot_name == plot_name)
+            .map(|c| &c.conversion)
+    }
+
+    fn set(&mut self, plot_name: &str, conversion: Option<UnitConversion>) {
+        self.conversions.retain(|c| c.plot_name != plot_name);
+        if let Some(conversion) = conversion {
+            self.conversions.push(PlotUnitConversion {
+                plot_name: plot_name.to_owned(),
+                conversion,
+            });
+        }
+    }
+
+    /// Shows the window content where users can select a unit conversion for each plot
+    pub fn show(&mut self, ui: &mut egui::Ui, plot_names: &[&str]) {
+        egui::Grid::new("unit_conversions").show(ui, |ui| {
+            for name in plot_names {
+                ui.label(RichText::new(*name).strong());
+                let current = self.get(name).cloned();
+                let mut selected = current.clone();
+                egui::ComboBox::from_id_salt(*name)
+                    .selected_text(selected.as_ref().map_or("None", |c| c.description()))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut selected, None, "None");
+                        for preset in UnitConversion::PRESETS {
+                            let description = preset.description();
+                            ui.selectable_value(&mut selected, Some(preset), description);
+                        }
+                        if ui
+                            .selectable_label(
+                                matches!(selected, Some(UnitConversion::Custom { .. })),
+                                "Custom",
+                            )
+                            .clicked()
+                        {
+                            selected = Some(UnitConversion::Custom {
+                                scale: 1.0,
+                                offset: 0.0,
+                                unit: String::new(),
+                            });
+                        }
+                    });
+                if let Some(UnitConversion::Custom {
+                    scale,
+                    offset,
+                    unit,
+                }) = &mut selected
+                {
+                    ui.horizontal(|ui| {
+                        ui.label("×");
+                        ui.add(egui::DragValue::new(scale).speed(0.01));
+                        ui.label("+");
+                        ui.add(egui::DragValue::new(offset).speed(0.1));
+                        ui.label("Unit");
+                        ui.add(egui::TextEdit::singleline(unit).desired_width(40.0));
+                    });
+                }
+                if selected != current {
+                    self.set(name, selected);
+                }
+                ui.end_row();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_celsius_to_fahrenheit() {
+        let conversion = UnitConversion::CelsiusToFahrenheit;
+        let (scale, offset) = conversion.scale_offset();
+        assert_eq!(100.0 * scale + offset, 212.0);
+        assert_eq!(-40.0 * scale + offset, -40.0);
+        assert_eq!(conversion.unit(), "°F");
+    }
+
+    #[test]
+    fn test_set_replaces_and_removes() {
+        let mut conversions = UnitConversions::default();
+        conversions.set("Runtime", Some(UnitConversion::HoursToMinutes));
+        conversions.set("Runtime", Some(UnitConversion::MinutesToHours));
+        assert_eq!(
+            conversions.get("Runtime"),
+            Some(&UnitConversion::MinutesToHours)
+        );
+        conversions.set("Runtime", None);
+        assert_eq!(conversions.get("Runtime"), None);
+    }
+}
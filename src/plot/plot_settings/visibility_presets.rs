@@ -0,0 +1,112 @@
+use egui::RichText;
+use egui_phosphor::regular;
+use serde::{Deserialize, Serialize};
+
+use super::plot_filter::PlotNameFilter;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct VisibilityPreset {
+    name: String,
+    // Names of the plots that are shown when the preset is applied, all other plots are hidden
+    shown_plots: Vec<String>,
+}
+
+/// Named sets of shown plots that can be reapplied with one click, e.g. for an analysis that is repeated across many files.
+///
+/// Presets are keyed by plot names so they apply to any log with plots of the same names.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct VisibilityPresets {
+    presets: Vec<VisibilityPreset>,
+    #[serde(skip)]
+    new_preset_name: String,
+}
+
+impl VisibilityPresets {
+    /// Saves the plots that are currently shown in the `filter` as a preset, replacing any preset with the same name
+    fn save(&mut self, name: &str, filter: &PlotNameFilter) {
+        let shown_plots = filter.shown_plot_names().map(ToOwned::to_owned).collect();
+        if let Some(preset) = self.presets.iter_mut().find(|p| p.name == name) {
+            preset.shown_plots = shown_plots;
+        } else {
+            self.presets.push(VisibilityPreset {
+                name: name.to_owned(),
+                shown_plots,
+            });
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, filter: &mut PlotNameFilter) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Presets").strong());
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_preset_name)
+                    .hint_text("Preset name")
+                    .desired_width(120.0),
+            );
+            let name = self.new_preset_name.trim().to_owned();
+            if ui
+                .add_enabled(
+                    !name.is_empty(),
+                    egui::Button::new(format!("{} Save", regular::FLOPPY_DISK)),
+                )
+                .on_hover_text("Save the shown plots as a preset")
+                .clicked()
+            {
+                self.save(&name, filter);
+                self.new_preset_name.clear();
+            }
+        });
+        let mut remove = None;
+        ui.horizontal_wrapped(|ui| {
+            for (idx, preset) in self.presets.iter().enumerate() {
+                let resp = ui.button(&preset.name).on_hover_text(format!(
+                    "Show: {}\nRight click to delete",
+                    preset.shown_plots.join(", ")
+                ));
+                if resp.clicked() {
+                    filter.show_only(&preset.shown_plots);
+                }
+                resp.context_menu(|ui| {
+                    if ui
+                        .button(format!("{} Delete preset", regular::TRASH))
+                        .clicked()
+                    {
+                        remove = Some(idx);
+                        ui.close_menu();
+                    }
+                });
+            }
+        });
+        if let Some(idx) = remove {
+            self.presets.remove(idx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::plot_filter::PlotNameShow;
+    use super::*;
+
+    #[test]
+    fn test_save_and_apply_preset() {
+        let mut filter = PlotNameFilter::default();
+        filter.add_plot(PlotNameShow::new("RPM".into(), true));
+        filter.add_plot(PlotNameShow::new("Current".into(), false));
+        filter.add_plot(PlotNameShow::new("Vbat".into(), true));
+        let mut presets = VisibilityPresets::default();
+        presets.save("engine", &filter);
+        assert_eq!(presets.presets[0].shown_plots, ["RPM", "Vbat"]);
+
+        filter.set_show_all();
+        filter.show_only(&presets.presets[0].shown_plots);
+        let shown: Vec<&str> = filter.shown_plot_names().collect();
+        assert_eq!(shown, ["RPM", "Vbat"]);
+
+        // Saving with the same name replaces the preset
+        filter.set_hide_all();
+        presets.save("engine", &filter);
+        assert_eq!(presets.presets.len(), 1);
+        assert!(presets.presets[0].shown_plots.is_empty());
+    }
+}
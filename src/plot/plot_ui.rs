@@ -1,14 +1,41 @@
 use egui::{Key, RichText};
 use egui_phosphor::regular;
+use plot_util::Plots;
 
-use super::{axis_config::AxisConfig, plot_settings::PlotSettings};
+#[cfg(not(target_arch = "wasm32"))]
+use super::csv_export::CsvExport;
+use super::{
+    axis_config::AxisConfig,
+    click_delta::ClickDelta,
+    gap_finder::GapFinder,
+    legend_settings::LegendSettings,
+    number_format::NumberFormat,
+    plot_settings::PlotSettings,
+    plot_window::{self, PlotWindow},
+    spectrum::Spectrum,
+    util,
+    xy_plot::XyPlot,
+};
 
 // filter settings should be refactored out to be a standalone thing, maybe together with loaded_logs_ui
+#[allow(
+    clippy::too_many_arguments,
+    reason = "They are needed. Maybe a refactor could group some of them."
+)]
 pub fn show_settings_grid(
     ui: &mut egui::Ui,
     line_width: &mut f32,
     axis_cfg: &mut AxisConfig,
+    plots: &Plots,
     plot_settings: &mut PlotSettings,
+    click_delta: &mut ClickDelta,
+    plot_windows: &mut Vec<PlotWindow>,
+    xy_plot: &mut XyPlot,
+    gap_finder: &mut GapFinder,
+    spectrum: &mut Spectrum,
+    #[cfg(not(target_arch = "wasm32"))] csv_export: &mut CsvExport,
+    legend_cfg: &mut LegendSettings,
+    number_format: &mut NumberFormat,
 ) {
     ui.horizontal_wrapped(|ui| {
         plot_settings.show(ui);
@@ -19,16 +46,55 @@ pub fn show_settings_grid(
         }
         if axis_cfg.ui_visible {
             let mut open: bool = axis_cfg.ui_visible;
+            let plot_names = plot_settings.plot_names();
             egui::Window::new(axis_cfg_str)
                 .open(&mut open)
                 .show(ui.ctx(), |ui| {
-                    axis_cfg.toggle_axis_cfg_ui(ui);
+                    axis_cfg.toggle_axis_cfg_ui(ui, &plot_names);
                 });
             axis_cfg.ui_visible = open;
         }
         if ui.ctx().input(|i| i.key_pressed(Key::Escape)) {
             axis_cfg.ui_visible = false;
         }
+        axis_cfg.reset_zoom_ui(ui, |ui| util::y_extremes_ui(ui, plots, plot_settings));
+        axis_cfg.goto_time_ui(ui);
+        plot_window::open_plot_window_button(ui, plot_windows);
+        if ui
+            .button(RichText::new(format!("{} XY plot", regular::CHART_SCATTER)))
+            .on_hover_text("Plot one series against another instead of against time")
+            .clicked()
+        {
+            xy_plot.toggle_open();
+        }
+        if ui
+            .button(RichText::new(format!("{} Gaps", regular::LINK_BREAK)))
+            .on_hover_text("Find gaps in a series, e.g. dropouts in telemetry")
+            .clicked()
+        {
+            gap_finder.toggle_open();
+        }
+        if ui
+            .button(RichText::new(format!("{} Spectrum", regular::WAVE_SINE)))
+            .on_hover_text("Show the frequency spectrum of a series, e.g. to analyse vibrations")
+            .clicked()
+        {
+            spectrum.toggle_open();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if ui
+            .button(RichText::new(format!("{} Export to CSV", regular::EXPORT)))
+            .on_hover_text("Export the shown series to CSV, with the start date offsets applied")
+            .clicked()
+        {
+            csv_export.toggle_open();
+        }
+        ui.menu_button(format!("{} Click delta", regular::RULER), |ui| {
+            ui.label("Shift+click two points in a plot to measure the delta between them");
+            click_delta.settings_ui(ui);
+        });
+        legend_cfg.show(ui);
+        number_format.show(ui);
         ui.label("Line width");
         ui.add(
             egui::DragValue::new(line_width)
@@ -0,0 +1,108 @@
+use egui::{RichText, ViewportBuilder, ViewportClass, ViewportId};
+use egui_phosphor::regular;
+
+/// An additional window that shows a subset of the loaded plots.
+///
+/// The plots are painted from the same [`plot_util::Plots`] and with the same link group as the main plot area,
+/// so they pan and zoom together with it. On native the window is a separate viewport that can be moved to another monitor.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PlotWindow {
+    id: u16,
+    open: bool,
+    // Names of the plots that are shown in the window
+    plot_names: Vec<String>,
+}
+
+impl PlotWindow {
+    pub fn new(id: u16) -> Self {
+        Self {
+            id,
+            open: true,
+            plot_names: Vec::new(),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn title(&self) -> String {
+        format!("Plots #{}", self.id)
+    }
+
+    /// Shows the window, `paint_plots` is called with the names of the plots to show.
+    ///
+    /// # Arguments
+    /// - `all_plot_names` The names of all loaded plots which the user can select from
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        all_plot_names: &[&str],
+        mut paint_plots: impl FnMut(&mut egui::Ui, &[String]),
+    ) {
+        let title = self.title();
+        let viewport_id = ViewportId::from_hash_of(("plot_window", self.id));
+        let builder = ViewportBuilder::default()
+            .with_title(&title)
+            .with_inner_size([1000.0, 700.0]);
+        ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+            if class == ViewportClass::Embedded {
+                // Multiple viewports are not supported (e.g. on web) so we fall back to a regular window
+                let mut open = self.open;
+                egui::Window::new(&title)
+                    .id(egui::Id::new(viewport_id))
+                    .open(&mut open)
+                    .default_size([800.0, 500.0])
+                    .show(ctx, |ui| self.ui(ui, all_plot_names, &mut paint_plots));
+                self.open = open;
+            } else {
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    self.ui(ui, all_plot_names, &mut paint_plots);
+                });
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    self.open = false;
+                }
+            }
+        });
+    }
+
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        all_plot_names: &[&str],
+        paint_plots: &mut impl FnMut(&mut egui::Ui, &[String]),
+    ) {
+        // Plots keep their state in memory by ID, so the IDs need to be unique from the ones in the main plot area
+        ui.push_id(self.id, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for name in all_plot_names {
+                    let mut shown = self.plot_names.iter().any(|n| n == name);
+                    if ui.toggle_value(&mut shown, *name).clicked() {
+                        if shown {
+                            self.plot_names.push((*name).to_owned());
+                        } else {
+                            self.plot_names.retain(|n| n != name);
+                        }
+                    }
+                }
+            });
+            if self.plot_names.is_empty() {
+                ui.label("Select the plots to show in this window");
+            } else {
+                ui.vertical(|ui| paint_plots(ui, &self.plot_names));
+            }
+        });
+    }
+}
+
+/// Shows the button for opening a new [`PlotWindow`]
+pub fn open_plot_window_button(ui: &mut egui::Ui, plot_windows: &mut Vec<PlotWindow>) {
+    if ui
+        .button(RichText::new(format!("{} New window", regular::APP_WINDOW)))
+        .on_hover_text("Open a window that shows a selection of the plots and pans and zooms together with the other plots")
+        .clicked()
+    {
+        let next_id = plot_windows.iter().map(|w| w.id).max().unwrap_or(0) + 1;
+        plot_windows.push(PlotWindow::new(next_id));
+    }
+}
@@ -0,0 +1,193 @@
+use egui::{Align2, Key, Modifiers};
+use egui_plot::{MarkerShape, PlotPoint, PlotUi, Points, Text, VLine};
+use plot_util::PlotValues;
+use serde::{Deserialize, Serialize};
+
+use super::PlotType;
+
+/// A cursor that is moved between the samples of a plot with the arrow keys.
+///
+/// Left/right moves the cursor to the previous/next sample of the selected plot and up/down selects
+/// the previous/next plot in the same plot area. Escape removes the cursor.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SampleCursor {
+    // Which plot type the cursor belongs to
+    plot_type: Option<PlotType>,
+    // Label of the plot that the cursor is on
+    plot_label: Option<String>,
+    sample_idx: usize,
+}
+
+impl SampleCursor {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Moves the cursor according to the arrow keys that were pressed, `plots` are the plots shown in the plot area.
+    ///
+    /// The keys are only consumed if the plot area is hovered and no other widget has keyboard focus,
+    /// so they are still available for e.g. scrolling and text editing elsewhere.
+    pub fn handle_input(
+        &mut self,
+        plot_ui: &mut PlotUi,
+        plot_type: PlotType,
+        plots: &[&PlotValues],
+    ) {
+        let ctx = plot_ui.ctx().clone();
+        if !plot_ui.response().hovered() || ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        let [left, right, up, down, escape] = ctx.input_mut(|i| {
+            [
+                Key::ArrowLeft,
+                Key::ArrowRight,
+                Key::ArrowUp,
+                Key::ArrowDown,
+                Key::Escape,
+            ]
+            .map(|key| i.consume_key(Modifiers::NONE, key))
+        });
+        if escape {
+            self.reset();
+            return;
+        }
+        if !(left || right || up || down) || plots.is_empty() {
+            return;
+        }
+
+        let current = self.selected_sample(plot_type, plots);
+        let plot_idx = match self.selected_plot_idx(plot_type, plots) {
+            Some(idx) if up => (idx + plots.len() - 1) % plots.len(),
+            Some(idx) if down => (idx + 1) % plots.len(),
+            Some(idx) => idx,
+            None => 0,
+        };
+        let points = plots[plot_idx].raw_plot();
+        if points.is_empty() {
+            return;
+        }
+        // Start at the sample closest in time to the current one, or the pointer if there's no current sample
+        let time = current.map_or_else(
+            || {
+                plot_ui
+                    .pointer_coordinate()
+                    .map_or_else(|| plot_ui.plot_bounds().center().x, |p| p.x)
+            },
+            |(_, [x, _])| x,
+        );
+        let sample_idx = if current.is_some() && !(up || down) {
+            let idx = self.sample_idx.min(points.len() - 1);
+            if left {
+                idx.saturating_sub(1)
+            } else if right {
+                (idx + 1).min(points.len() - 1)
+            } else {
+                idx
+            }
+        } else {
+            closest_sample_idx(points, time)
+        };
+
+        self.plot_type = Some(plot_type);
+        self.plot_label = Some(plots[plot_idx].label().to_owned());
+        self.sample_idx = sample_idx;
+
+        // Pan to keep the selected sample in view
+        let [x, _] = points[sample_idx];
+        let mut bounds = plot_ui.plot_bounds();
+        if !bounds.range_x().contains(&x) {
+            bounds.translate_x(x - bounds.center().x);
+            plot_ui.set_plot_bounds(bounds);
+        }
+    }
+
+    fn selected_plot_idx(&self, plot_type: PlotType, plots: &[&PlotValues]) -> Option<usize> {
+        if self.plot_type != Some(plot_type) {
+            return None;
+        }
+        let label = self.plot_label.as_deref()?;
+        plots.iter().position(|pv| pv.label() == label)
+    }
+
+    /// Returns the plot and the (raw) sample that the cursor is on if it is in the plot area of `plot_type`
+    pub fn selected_sample<'pv>(
+        &self,
+        plot_type: PlotType,
+        plots: &[&'pv PlotValues],
+    ) -> Option<(&'pv PlotValues, [f64; 2])> {
+        let pv = plots[self.selected_plot_idx(plot_type, plots)?];
+        let points = pv.raw_plot();
+        let idx = self.sample_idx.min(points.len().checked_sub(1)?);
+        Some((pv, points[idx]))
+    }
+
+    /// Paints the cursor at `point` (as it is painted in the plot area) along with the `readout` of the sample.
+    pub fn ui(plot_ui: &mut PlotUi, point: PlotPoint, readout: String) {
+        let color = plot_ui.ctx().style().visuals.strong_text_color();
+        plot_ui.vline(VLine::new(point.x).color(color.gamma_multiply(0.5)));
+        // The sample might not be painted, e.g. non-positive values on a logarithmic axis
+        let text_pos = if point.y.is_finite() {
+            plot_ui.points(
+                Points::new([point.x, point.y])
+                    .shape(MarkerShape::Circle)
+                    .filled(false)
+                    .radius(6.0)
+                    .color(color),
+            );
+            point
+        } else {
+            PlotPoint::new(point.x, plot_ui.plot_bounds().center().y)
+        };
+        plot_ui.text(
+            Text::new(text_pos, readout)
+                .color(color)
+                .anchor(Align2::LEFT_BOTTOM),
+        );
+    }
+}
+
+/// Returns the index of the point in `points` (sorted by time) that is closest in time to `time`
+fn closest_sample_idx(points: &[[f64; 2]], time: f64) -> usize {
+    let idx = points.partition_point(|[x, _]| *x < time);
+    if idx == 0 {
+        return 0;
+    }
+    if idx == points.len() {
+        return points.len() - 1;
+    }
+    if time - points[idx - 1][0] <= points[idx][0] - time {
+        idx - 1
+    } else {
+        idx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_sample_idx() {
+        let points = [[0.0, 1.0], [10.0, 2.0], [20.0, 3.0]];
+        assert_eq!(closest_sample_idx(&points, -5.0), 0);
+        assert_eq!(closest_sample_idx(&points, 4.0), 0);
+        assert_eq!(closest_sample_idx(&points, 6.0), 1);
+        assert_eq!(closest_sample_idx(&points, 20.0), 2);
+        assert_eq!(closest_sample_idx(&points, 100.0), 2);
+    }
+
+    #[test]
+    fn test_selected_sample_only_in_its_plot_area() {
+        let pv = PlotValues::new(vec![[0.0, 1.0], [10.0, 2.0]], "RPM".to_owned(), 1);
+        let cursor = SampleCursor {
+            plot_type: Some(PlotType::Thousands),
+            plot_label: Some(pv.label().to_owned()),
+            sample_idx: 5,
+        };
+        let (_, sample) = cursor.selected_sample(PlotType::Thousands, &[&pv]).unwrap();
+        assert_eq!(sample, [10.0, 2.0]);
+        assert!(cursor
+            .selected_sample(PlotType::Percentage, &[&pv])
+            .is_none());
+    }
+}
@@ -0,0 +1,306 @@
+use std::f64::consts::PI;
+
+use egui::{Color32, RichText};
+use egui_phosphor::regular;
+use egui_plot::{Line, Plot};
+use plot_util::{PlotValues, Plots};
+use rustfft::{num_complex::Complex, FftPlanner};
+
+use super::{plot_settings::PlotSettings, util};
+
+// Resampling a long series at the interval of its fastest part could use a lot of memory, so the spectrum is only computed up to this many samples
+const MAX_SAMPLES: usize = 1 << 22;
+
+/// The window function that is applied to the samples before the FFT to reduce spectral leakage
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+enum WindowFunction {
+    Rectangular,
+    #[default]
+    Hann,
+    Hamming,
+    Blackman,
+}
+
+impl WindowFunction {
+    fn all() -> [Self; 4] {
+        [Self::Rectangular, Self::Hann, Self::Hamming, Self::Blackman]
+    }
+
+    /// The weight of the `n`th of `len` samples
+    fn weight(self, n: usize, len: usize) -> f64 {
+        if len < 2 {
+            return 1.0;
+        }
+        let x = 2.0 * PI * n as f64 / (len - 1) as f64;
+        match self {
+            Self::Rectangular => 1.0,
+            Self::Hann => 0.5 - 0.5 * x.cos(),
+            Self::Hamming => 0.54 - 0.46 * x.cos(),
+            Self::Blackman => 0.42 - 0.5 * x.cos() + 0.08 * (2.0 * x).cos(),
+        }
+    }
+}
+
+/// A spectrum that was computed for a series
+#[derive(Debug, PartialEq)]
+struct ComputedSpectrum {
+    label: String,
+    unit: Option<String>,
+    window: WindowFunction,
+    sample_rate_hz: f64,
+    sample_count: usize,
+    // Amplitude by frequency in Hz
+    points: Vec<[f64; 2]>,
+}
+
+/// A window that shows the amplitude spectrum of a series, e.g. for analysing vibrations.
+///
+/// The series is resampled to a uniform grid at its median sample interval by linear interpolation before the FFT,
+/// the mean is removed so the DC component doesn't hide the rest of the spectrum.
+#[derive(Debug, Default, PartialEq)]
+pub struct Spectrum {
+    open: bool,
+    series_label: Option<String>,
+    window: WindowFunction,
+    only_visible_range: bool,
+    // Only computed on demand as it can be slow for large series
+    computed: Option<Result<ComputedSpectrum, String>>,
+}
+
+impl Spectrum {
+    pub fn toggle_open(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Shows the window if it is open, `visible_x_range` is the time range (in ns) currently shown in the plots.
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        plots: &Plots,
+        plot_settings: &PlotSettings,
+        visible_x_range: Option<(f64, f64)>,
+    ) {
+        if !self.open {
+            return;
+        }
+        let series = util::shown_series(plots, plot_settings);
+        let mut open = self.open;
+        egui::Window::new(RichText::new(format!("{} Spectrum", regular::WAVE_SINE)))
+            .open(&mut open)
+            .default_size([600.0, 450.0])
+            .show(ctx, |ui| {
+                self.ui(ui, &series, plot_settings, visible_x_range);
+            });
+        self.open = open;
+    }
+
+    fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        series: &[&PlotValues],
+        plot_settings: &PlotSettings,
+        visible_x_range: Option<(f64, f64)>,
+    ) {
+        ui.horizontal(|ui| {
+            util::series_combo_box(ui, "spectrum_series", &mut self.series_label, series);
+            egui::ComboBox::from_label("Window")
+                .selected_text(self.window.to_string())
+                .show_ui(ui, |ui| {
+                    for window in WindowFunction::all() {
+                        ui.selectable_value(&mut self.window, window, window.to_string());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.add_enabled(
+                visible_x_range.is_some(),
+                egui::Checkbox::new(&mut self.only_visible_range, "Only the visible time range"),
+            );
+            let selected = self
+                .series_label
+                .as_deref()
+                .and_then(|label| series.iter().find(|pv| pv.label() == label));
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new("Compute"))
+                .clicked()
+            {
+                if let Some(pv) = selected {
+                    let x_range = visible_x_range.filter(|_| self.only_visible_range);
+                    self.computed = Some(compute(pv, plot_settings, self.window, x_range));
+                }
+            }
+        });
+        match &self.computed {
+            None => {
+                ui.label("Select a series and compute its spectrum");
+            }
+            Some(Err(err_msg)) => {
+                ui.label(RichText::new(format!("⚠ {err_msg}")).color(Color32::YELLOW));
+            }
+            Some(Ok(spectrum)) => {
+                ui.label(format!(
+                    "{}: {} samples resampled at {:.3} Hz, {} window",
+                    spectrum.label, spectrum.sample_count, spectrum.sample_rate_hz, spectrum.window
+                ));
+                let y_label = match &spectrum.unit {
+                    Some(unit) => format!("Amplitude [{unit}]"),
+                    None => "Amplitude".to_owned(),
+                };
+                Plot::new("spectrum")
+                    .x_axis_label("Frequency [Hz]")
+                    .y_axis_label(y_label)
+                    .show(ui, |plot_ui| {
+                        plot_ui.line(Line::new(spectrum.points.clone()).name(&spectrum.label));
+                    });
+            }
+        }
+    }
+}
+
+fn compute(
+    pv: &PlotValues,
+    plot_settings: &PlotSettings,
+    window: WindowFunction,
+    x_range: Option<(f64, f64)>,
+) -> Result<ComputedSpectrum, String> {
+    let conversion = plot_settings.unit_conversion(pv.name());
+    let (scale, offset) = conversion.map_or((1.0, 0.0), |c| c.scale_offset());
+    let points: Vec<[f64; 2]> = pv
+        .raw_plot()
+        .iter()
+        .filter(|[t, _]| x_range.is_none_or(|(min, max)| (min..=max).contains(t)))
+        .map(|&[t, y]| [t, y * scale + offset])
+        .collect();
+    let (interval_ns, samples) = resample_uniform(&points)?;
+    let sample_rate_hz = 1_000_000_000.0 / interval_ns;
+    Ok(ComputedSpectrum {
+        label: pv.label().to_owned(),
+        unit: plot_settings.unit(pv).map(ToOwned::to_owned),
+        window,
+        sample_rate_hz,
+        sample_count: samples.len(),
+        points: amplitude_spectrum(&samples, sample_rate_hz, window),
+    })
+}
+
+/// Resamples the `points` (sorted by time, in ns) to a uniform grid at their median interval by linear interpolation
+///
+/// Returns the interval in ns and the samples.
+fn resample_uniform(points: &[[f64; 2]]) -> Result<(f64, Vec<f64>), String> {
+    let mut intervals: Vec<f64> = points
+        .windows(2)
+        .map(|w| w[1][0] - w[0][0])
+        .filter(|dt| *dt > 0.0)
+        .collect();
+    if intervals.is_empty() {
+        return Err("At least two samples at different times are needed".to_owned());
+    }
+    let mid = intervals.len() / 2;
+    let (_, median, _) = intervals.select_nth_unstable_by(mid, f64::total_cmp);
+    let interval = *median;
+    let (first, last) = (points[0][0], points[points.len() - 1][0]);
+    let sample_count = ((last - first) / interval) as usize + 1;
+    if sample_count > MAX_SAMPLES {
+        return Err(format!(
+            "Resampling gives {sample_count} samples which is more than the maximum of {MAX_SAMPLES}, zoom in and use only the visible time range"
+        ));
+    }
+    let mut samples = Vec::with_capacity(sample_count);
+    let mut idx = 0;
+    for n in 0..sample_count {
+        let t = first + n as f64 * interval;
+        // The grid and the points are sorted by time, so the points are walked once
+        while idx + 2 < points.len() && points[idx + 1][0] < t {
+            idx += 1;
+        }
+        let ([t0, v0], [t1, v1]) = (points[idx], points[(idx + 1).min(points.len() - 1)]);
+        let sample = if t1 > t0 {
+            v0 + (v1 - v0) * ((t - t0) / (t1 - t0)).clamp(0.0, 1.0)
+        } else {
+            v1
+        };
+        samples.push(sample);
+    }
+    Ok((interval, samples))
+}
+
+/// Returns the single-sided amplitude spectrum of the uniformly spaced `samples`, as amplitude by frequency in Hz.
+///
+/// The amplitude is corrected for the gain of the `window`, so a sine wave at the frequency of a bin has its amplitude at that bin.
+fn amplitude_spectrum(
+    samples: &[f64],
+    sample_rate_hz: f64,
+    window: WindowFunction,
+) -> Vec<[f64; 2]> {
+    let len = samples.len();
+    if len == 0 {
+        return Vec::new();
+    }
+    let mean = samples.iter().sum::<f64>() / len as f64;
+    let weights: Vec<f64> = (0..len).map(|n| window.weight(n, len)).collect();
+    let mut buf: Vec<Complex<f64>> = samples
+        .iter()
+        .zip(&weights)
+        .map(|(s, w)| Complex::new((s - mean) * w, 0.0))
+        .collect();
+    FftPlanner::new().plan_fft_forward(len).process(&mut buf);
+    let weight_sum: f64 = weights.iter().sum();
+    let bin_width_hz = sample_rate_hz / len as f64;
+    buf.iter()
+        .take(len / 2 + 1)
+        .enumerate()
+        .map(|(k, c)| {
+            // The energy of the other bins is split between the positive and negative frequencies
+            let single_sided = if k == 0 || 2 * k == len { 1.0 } else { 2.0 };
+            [
+                k as f64 * bin_width_hz,
+                c.norm() * single_sided / weight_sum,
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amplitude_spectrum_of_sine() {
+        let sample_rate_hz = 100.0;
+        // 5 Hz with an amplitude of 2 for 2 seconds
+        let samples: Vec<f64> = (0..200)
+            .map(|n| 3.0 + 2.0 * (2.0 * PI * 5.0 * f64::from(n) / sample_rate_hz).sin())
+            .collect();
+        for window in WindowFunction::all() {
+            let spectrum = amplitude_spectrum(&samples, sample_rate_hz, window);
+            assert_eq!(spectrum.len(), 101);
+            let [peak_hz, peak_amplitude] = spectrum
+                .iter()
+                .copied()
+                .max_by(|a, b| a[1].total_cmp(&b[1]))
+                .expect("there are bins");
+            assert_eq!(peak_hz, 5.0, "{window}");
+            assert!(
+                (peak_amplitude - 2.0).abs() < 0.05,
+                "{window}: {peak_amplitude}"
+            );
+            // The mean is removed
+            assert!(spectrum[0][1] < 0.05, "{window}");
+        }
+    }
+
+    #[test]
+    fn test_resample_uniform() {
+        let points = [[0.0, 0.0], [10.0, 10.0], [20.0, 20.0], [40.0, 0.0]];
+        let (interval, samples) = resample_uniform(&points).expect("enough points");
+        assert_eq!(interval, 10.0);
+        assert_eq!(samples, [0.0, 10.0, 20.0, 10.0, 0.0]);
+    }
+
+    #[test]
+    fn test_resample_uniform_too_few_points() {
+        assert!(resample_uniform(&[]).is_err());
+        assert!(resample_uniform(&[[1.0, 0.0]]).is_err());
+        assert!(resample_uniform(&[[1.0, 0.0], [1.0, 1.0]]).is_err());
+    }
+}
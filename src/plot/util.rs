@@ -1,13 +1,21 @@
+use std::{hash::Hash, path::PathBuf};
+
+use egui::RichText;
 use log_if::prelude::*;
-use plot_util::{Plots, StoredPlotLabels};
+use plot_util::{PlotData, PlotValues, Plots, StoredPlotLabels};
 
-use crate::app::supported_formats::SupportedFormat;
+use crate::app::supported_formats::logs::parse_info::ParseInfo;
 
-use super::plot_settings::{date_settings::LoadedLogSettings, PlotSettings};
+use super::{
+    plot_settings::{date_settings::LoadedLogSettings, PlotSettings},
+    PlotType,
+};
 
 pub fn add_plot_data_to_plot_collections(
     plots: &mut Plots,
-    data: &SupportedFormat,
+    data: &dyn Plotable,
+    parse_info: Option<ParseInfo>,
+    source_path: Option<PathBuf>,
     plot_settings: &mut PlotSettings,
 ) {
     // This is how all logs get their log_id, and how each plot for each log gets their log_id
@@ -18,21 +26,65 @@ pub fn add_plot_data_to_plot_collections(
         data.descriptive_name().to_owned(),
         data.first_timestamp(),
         data.metadata(),
-        data.parse_info(),
+        parse_info,
+        source_path,
     ));
+    add_plots(plots, data, data_id, plot_settings);
+}
+
+/// Replaces the plots (and labels) of the log with `log_id` with those of `data`, e.g. after the log was parsed again from disk.
+///
+/// The plots that were also in the log before it was reloaded keep their color.
+pub fn reload_plot_data(
+    plots: &mut Plots,
+    log_id: u16,
+    data: &dyn Plotable,
+    parse_info: Option<ParseInfo>,
+    plot_settings: &mut PlotSettings,
+) {
+    let mut removed = plots.percentage_mut().remove_log(log_id);
+    removed.extend(plots.one_to_hundred_mut().remove_log(log_id));
+    removed.extend(plots.thousands_mut().remove_log(log_id));
+
+    add_plots(plots, data, log_id, plot_settings);
+    let restore_colors = |plot_data: &mut PlotData| {
+        for pv in plot_data.plots_as_mut() {
+            if pv.log_id() != log_id {
+                continue;
+            }
+            if let Some(old) = removed.iter().find(|old| old.name() == pv.name()) {
+                *pv.get_color_mut() = old.get_color();
+            }
+        }
+    };
+    restore_colors(plots.percentage_mut());
+    restore_colors(plots.one_to_hundred_mut());
+    restore_colors(plots.thousands_mut());
+
+    plot_settings.log_reloaded(log_id, data.first_timestamp(), data.metadata(), parse_info);
+}
+
+fn add_plots(
+    plots: &mut Plots,
+    data: &dyn Plotable,
+    data_id: u16,
+    plot_settings: &mut PlotSettings,
+) {
     for raw_plot in data.raw_plots() {
-        match raw_plot.expected_range() {
-            ExpectedPlotRange::Percentage => {
+        let plot_type =
+            plot_settings.plot_area_for_new_plot(raw_plot.name(), raw_plot.expected_range().into());
+        match plot_type {
+            PlotType::Percentage => {
                 plots
                     .percentage_mut()
                     .add_plot_if_not_exists(raw_plot, data_id);
             }
-            ExpectedPlotRange::OneToOneHundred => {
+            PlotType::Hundreds => {
                 plots
                     .one_to_hundred_mut()
                     .add_plot_if_not_exists(raw_plot, data_id);
             }
-            ExpectedPlotRange::Thousands => {
+            PlotType::Thousands => {
                 plots
                     .thousands_mut()
                     .add_plot_if_not_exists(raw_plot, data_id);
@@ -60,3 +112,121 @@ pub fn add_plot_data_to_plot_collections(
         }
     }
 }
+
+/// Returns the plots in all plot areas that are not filtered out
+pub fn shown_series<'a>(plots: &'a Plots, plot_settings: &'a PlotSettings) -> Vec<&'a PlotValues> {
+    [
+        plots.percentage(),
+        plots.one_to_hundred(),
+        plots.thousands(),
+    ]
+    .into_iter()
+    .flat_map(|plot_data| plot_settings.apply_filters(plot_data.plots()))
+    .collect()
+}
+
+/// Shows which of the shown series have the lowest and the highest value in each plot area that is shown,
+/// i.e. the series that the y-range is fitted to when the zoom is reset.
+pub fn y_extremes_ui(ui: &mut egui::Ui, plots: &Plots, plot_settings: &PlotSettings) {
+    let plot_areas = [
+        (
+            PlotType::Percentage,
+            plots.percentage(),
+            plot_settings.display_percentage(),
+        ),
+        (
+            PlotType::Hundreds,
+            plots.one_to_hundred(),
+            plot_settings.display_hundreds(),
+        ),
+        (
+            PlotType::Thousands,
+            plots.thousands(),
+            plot_settings.display_thousands(),
+        ),
+    ];
+    egui::Grid::new("y_extremes").show(ui, |ui| {
+        for (plot_type, plot_data, displayed) in plot_areas {
+            if !displayed {
+                continue;
+            }
+            let series: Vec<&PlotValues> = plot_settings.apply_filters(plot_data.plots()).collect();
+            let Some(((min, min_pv), (max, max_pv))) = y_extremes(&series, plot_settings) else {
+                continue;
+            };
+            ui.label(RichText::new(plot_type.to_string()).strong());
+            ui.label(format!("min {min:.2} {}", min_pv.label()));
+            ui.label(format!("max {max:.2} {}", max_pv.label()));
+            ui.end_row();
+        }
+    });
+}
+
+// A value and the series it is from
+type Extreme<'pv> = (f64, &'pv PlotValues);
+
+/// Returns the lowest and the highest (unit converted) value of the `series`, and the series they are from.
+fn y_extremes<'pv>(
+    series: &[&'pv PlotValues],
+    plot_settings: &PlotSettings,
+) -> Option<(Extreme<'pv>, Extreme<'pv>)> {
+    let mut extremes: Option<(Extreme<'pv>, Extreme<'pv>)> = None;
+    for pv in series {
+        let Some((y_min, y_max)) = pv.y_min_max() else {
+            continue;
+        };
+        let (scale, offset) = plot_settings
+            .unit_conversion(pv.name())
+            .map_or((1.0, 0.0), |c| c.scale_offset());
+        // A negative scale swaps the min and max
+        let (a, b) = (y_min * scale + offset, y_max * scale + offset);
+        let (y_min, y_max) = (a.min(b), a.max(b));
+        match &mut extremes {
+            None => extremes = Some(((y_min, pv), (y_max, pv))),
+            Some((min, max)) => {
+                if y_min < min.0 {
+                    *min = (y_min, pv);
+                }
+                if y_max > max.0 {
+                    *max = (y_max, pv);
+                }
+            }
+        }
+    }
+    extremes
+}
+
+/// Shows a combo box for selecting one of the `series` by its label
+pub fn series_combo_box(
+    ui: &mut egui::Ui,
+    id_salt: impl Hash,
+    selected: &mut Option<String>,
+    series: &[&PlotValues],
+) {
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected.as_deref().unwrap_or("Select series"))
+        .show_ui(ui, |ui| {
+            for pv in series {
+                ui.selectable_value(selected, Some(pv.label().to_owned()), pv.label());
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_y_extremes() {
+        let plot_settings = PlotSettings::default();
+        let a = PlotValues::new(vec![[0.0, 1.0], [1.0, 2.0]], "a".into(), 1);
+        let b = PlotValues::new(vec![[0.0, -5.0], [1.0, 1.5]], "b".into(), 1);
+        let c = PlotValues::new(vec![[0.0, 0.0], [1.0, 100.0]], "c".into(), 2);
+        let Some(((min, min_pv), (max, max_pv))) = y_extremes(&[&a, &b, &c], &plot_settings) else {
+            panic!("Expected extremes of non-empty series");
+        };
+        assert_eq!((min, min_pv.label()), (-5.0, "b #1"));
+        assert_eq!((max, max_pv.label()), (100.0, "c #2"));
+        assert!(y_extremes(&[], &plot_settings).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use egui::RichText;
+use egui_phosphor::regular;
+use egui_plot::{Line, Plot, Points};
+use plot_util::{PlotValues, Plots};
+
+use super::{plot_settings::PlotSettings, util};
+
+/// A window that plots one series against another instead of against time, e.g. current vs. voltage.
+///
+/// The x-series is resampled onto the timestamps of the y-series by linear interpolation,
+/// so only the part where the series overlap in time is plotted.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct XyPlot {
+    open: bool,
+    // Labels of the selected series
+    x_label: Option<String>,
+    y_label: Option<String>,
+    show_points: bool,
+}
+
+impl XyPlot {
+    pub fn toggle_open(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Shows the window if it is open, the series to select from are the loaded plots that are not filtered out.
+    pub fn show(&mut self, ctx: &egui::Context, plots: &Plots, plot_settings: &PlotSettings) {
+        if !self.open {
+            return;
+        }
+        let series = util::shown_series(plots, plot_settings);
+        let mut open = self.open;
+        egui::Window::new(RichText::new(format!("{} XY plot", regular::CHART_SCATTER)))
+            .open(&mut open)
+            .default_size([600.0, 500.0])
+            .show(ctx, |ui| self.ui(ui, &series, plot_settings));
+        self.open = open;
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, series: &[&PlotValues], plot_settings: &PlotSettings) {
+        ui.horizontal(|ui| {
+            ui.label("X");
+            util::series_combo_box(ui, "xy_plot_x", &mut self.x_label, series);
+            ui.label("Y");
+            util::series_combo_box(ui, "xy_plot_y", &mut self.y_label, series);
+            ui.checkbox(&mut self.show_points, "Show points");
+        });
+        let find = |label: Option<&str>| {
+            label.and_then(|l| series.iter().find(|pv| pv.label() == l).copied())
+        };
+        let (Some(x), Some(y)) = (find(self.x_label.as_deref()), find(self.y_label.as_deref()))
+        else {
+            ui.label("Select the series to plot against each other");
+            return;
+        };
+        let scale_offset = |pv: &PlotValues| {
+            plot_settings
+                .unit_conversion(pv.name())
+                .map_or((1.0, 0.0), |c| c.scale_offset())
+        };
+        let (x_scale, x_offset) = scale_offset(x);
+        let (y_scale, y_offset) = scale_offset(y);
+        let points: Vec<[f64; 2]> = xy_points(x.raw_plot(), y.raw_plot())
+            .into_iter()
+            .map(|[x, y]| [x * x_scale + x_offset, y * y_scale + y_offset])
+            .collect();
+        if points.is_empty() {
+            ui.label("The selected series don't overlap in time");
+            return;
+        }
+        let color = y.get_color();
+        Plot::new("xy_plot")
+            .x_axis_label(plot_settings.series_label(x))
+            .y_axis_label(plot_settings.series_label(y))
+            .show(ui, |plot_ui| {
+                if self.show_points {
+                    plot_ui.points(Points::new(points).color(color).radius(2.0));
+                } else {
+                    plot_ui.line(Line::new(points).color(color));
+                }
+            });
+    }
+}
+
+/// Returns the points of `y` (sorted by time) paired with the value of `x` (sorted by time) at the same time.
+///
+/// Points of `y` outside the time range of `x` are skipped.
+fn xy_points(x: &[[f64; 2]], y: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    y.iter()
+        .filter_map(|&[t, y]| interpolate(x, t).map(|x| [x, y]))
+        .collect()
+}
+
+/// Returns the linearly interpolated value of `points` (sorted by time) at `time` or [`None`] if it is outside their time range.
+fn interpolate(points: &[[f64; 2]], time: f64) -> Option<f64> {
+    let idx = points.partition_point(|[t, _]| *t < time);
+    let [t1, v1] = *points.get(idx)?;
+    if t1 == time {
+        return Some(v1);
+    }
+    let [t0, v0] = points[idx.checked_sub(1)?];
+    Some(v0 + (v1 - v0) * (time - t0) / (t1 - t0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate() {
+        let points = [[0.0, 0.0], [10.0, 100.0], [20.0, 50.0]];
+        assert_eq!(interpolate(&points, 0.0), Some(0.0));
+        assert_eq!(interpolate(&points, 5.0), Some(50.0));
+        assert_eq!(interpolate(&points, 15.0), Some(75.0));
+        assert_eq!(interpolate(&points, 20.0), Some(50.0));
+        assert_eq!(interpolate(&points, -1.0), None);
+        assert_eq!(interpolate(&points, 21.0), None);
+        assert_eq!(interpolate(&[], 1.0), None);
+    }
+
+    #[test]
+    fn test_xy_points_without_overlap() {
+        let x = [[0.0, 1.0], [10.0, 2.0]];
+        assert!(xy_points(&x, &[[20.0, 5.0], [30.0, 6.0]]).is_empty());
+        assert_eq!(xy_points(&x, &[[5.0, 5.0], [30.0, 6.0]]), [[1.5, 5.0]]);
+    }
+}
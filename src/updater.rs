@@ -4,8 +4,10 @@
 )]
 use crate::{APP_NAME, APP_OWNER};
 use axoupdater::AxoupdateResult;
+use config::UpdateConfig;
 use std::{
     env,
+    ffi::OsString,
     fs::{self, File},
     io,
     path::{Path, PathBuf},
@@ -15,8 +17,15 @@ use std::{
 pub static APP_INSTALL_DIR: OnceLock<PathBuf> = OnceLock::new();
 /// Returns the parent of the parent of the executable directory due to the installation being done at <`target_dir`>/bin/<`new_plotinator_binary`>
 /// so if we point at /bin/<`current_exe`> the axoupdater would install the update at /bin/bin/<`new_exe`>, therefor we go one level higher
+///
+/// For non-standard installs the directory can be set with the [`INSTALL_DIR_ENV_VAR`] environment variable
+/// or in the [`UpdateConfig`] instead.
 pub fn get_app_install_dir() -> &'static PathBuf {
     APP_INSTALL_DIR.get_or_init(|| {
+        if let Some(install_dir) = custom_install_dir() {
+            log::info!("Custom install directory: {}", install_dir.display());
+            return install_dir;
+        }
         let exe_path = std::env::current_exe().expect("Could not find executable");
         log::info!("Executable path: {}", exe_path.display());
         exe_path
@@ -30,9 +39,22 @@ pub fn get_app_install_dir() -> &'static PathBuf {
 
 const DISABLE_UPDATES_FILE: &str = "plotinator_disable_updates";
 const BYPASS_UPDATES_ENV_VAR: &str = "PLOTINATOR_BYPASS_UPDATES";
+// Command line argument for skipping the updater for this run, e.g. when running a release build locally
+const NO_UPDATE_ARG: &str = "--no-update";
+const INSTALL_DIR_ENV_VAR: &str = "PLOTINATOR_INSTALL_DIR";
+// Authenticated requests to the GitHub API have a much higher rate limit
+const GITHUB_TOKEN_ENV_VAR: &str = "GITHUB_TOKEN";
+// Requests for the rate limit status don't count against the rate limit
+const GITHUB_RATE_LIMIT_URL: &str = "https://api.github.com/rate_limit";
+// The environment variables that reqwest takes the proxy of HTTPS requests from
+const PROXY_ENV_VARS: [&str; 4] = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"];
+// Set to install updates without relaunching as admin, for users that can't get admin rights
+#[cfg(target_os = "windows")]
+const NO_ELEVATION_ENV_VAR: &str = "PLOTINATOR_NO_ELEVATION";
 // Use this to debug the update workflow (or use the environment variable)
 const FORCE_UPGRADE: bool = false;
 
+mod config;
 mod ui;
 
 /// A wrapper around the [`axoupdater::AxoUpdater`] struct that sets up the updater with the correct parameters
@@ -53,7 +75,7 @@ impl PlotinatorUpdater {
             app_name: APP_NAME.to_owned(),
         });
         updater.disable_installer_output();
-        if let Ok(t) = env::var("GITHUB_TOKEN") {
+        if let Ok(t) = env::var(GITHUB_TOKEN_ENV_VAR) {
             updater.set_github_token(&t);
         }
 
@@ -110,6 +132,20 @@ fn is_admin_run_elevated() -> io::Result<bool> {
 
     if AdminCommand::is_elevated() {
         Ok(true)
+    } else if no_elevation() {
+        let install_dir = get_app_install_dir();
+        if is_dir_writable(install_dir) {
+            log::info!("Updating without elevated permissions");
+            Ok(true)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "Updating requires write access to '{dir}', but elevating permissions is disabled by {NO_ELEVATION_ENV_VAR}. Install {APP_NAME} in a directory you have write access to, or set {BYPASS_UPDATES_ENV_VAR}=1 to skip updates.",
+                    dir = install_dir.display()
+                ),
+            ))
+        }
     } else {
         if !pre_admin_window_user_clicked_update().unwrap_or(false) {
             return Ok(false);
@@ -167,9 +203,22 @@ pub fn update_if_applicable() -> axoupdater::AxoupdateResult<bool> {
                 return Ok(false);
             }
         } else {
+            let proxy = match apply_update_proxy() {
+                Ok(proxy) => proxy,
+                Err(e) => {
+                    ui::error_window::show_error_occurred(&e.to_string());
+                    return Ok(false);
+                }
+            };
             match is_update_available() {
                 Ok(is_update_available) => {
                     if is_update_available {
+                        if let Some(install_dir) = custom_install_dir() {
+                            if let Err(e) = validate_install_dir(&install_dir) {
+                                ui::error_window::show_error_occurred(&e.to_string());
+                                return Ok(false);
+                            }
+                        }
                         #[cfg(target_os = "windows")]
                         match is_admin_run_elevated() {
                             Ok(is_admin) => {
@@ -195,7 +244,21 @@ pub fn update_if_applicable() -> axoupdater::AxoupdateResult<bool> {
                     }
                     return Ok(false);
                 }
+                Err(e) if proxy.is_some() && is_proxy_error(&e) => {
+                    log::warn!("Update check failed connecting through the proxy: {e}");
+                    let proxy = proxy.unwrap_or_default();
+                    ui::error_window::show_error_occurred(&format!("Checking for updates failed connecting through the proxy '{proxy}'. Check the proxy set in the HTTPS_PROXY environment variable or in the update config. {APP_NAME} will start without checking for updates."));
+                    return Ok(false);
+                }
                 Err(e) => {
+                    if let Some(refusal) = refusal_of(&e) {
+                        log::warn!("Update check was refused ({refusal:?}): {e}");
+                        ui::error_window::show_error_occurred(&format!(
+                            "{} {APP_NAME} will start without checking for updates.",
+                            refusal.message()
+                        ));
+                        return Ok(false);
+                    }
                     log::error!("Error checking for update: {e}");
                     return Err(e);
                 }
@@ -205,48 +268,243 @@ pub fn update_if_applicable() -> axoupdater::AxoupdateResult<bool> {
     Ok(false)
 }
 
-/// Check for the environment variable to bypass updates
-fn bypass_updates() -> bool {
-    if let Ok(value) = env::var(BYPASS_UPDATES_ENV_VAR) {
-        if value == "1" || value.eq_ignore_ascii_case("true") {
-            log::info!("Update bypassed due to environment variable.");
-            return true;
+/// Returns true if the environment variable is set to `1` or `true`
+fn env_flag_is_set(env_var: &str) -> bool {
+    env::var(env_var).is_ok_and(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+}
+
+/// Why the GitHub API refused a request for the releases
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Refusal {
+    RateLimited,
+    InvalidToken,
+    /// GitHub answers `403 Forbidden` both when the rate limit is used up and when the token lacks permissions
+    Unknown,
+}
+
+impl Refusal {
+    fn message(self) -> String {
+        match self {
+            Self::RateLimited if env::var_os(GITHUB_TOKEN_ENV_VAR).is_some() => "Checking for updates failed because GitHub limits how many requests can be made, try again later.".to_owned(),
+            Self::RateLimited => format!("Checking for updates failed because GitHub limits how many requests can be made without authentication. Set the {GITHUB_TOKEN_ENV_VAR} environment variable to a GitHub access token to avoid this."),
+            Self::InvalidToken => format!("Checking for updates failed because GitHub rejected the access token in the {GITHUB_TOKEN_ENV_VAR} environment variable. It might be invalid or expired, replace it with a valid token or unset it."),
+            Self::Unknown => format!("Checking for updates failed because GitHub refused the request. Either too many requests were made, which is avoided by setting the {GITHUB_TOKEN_ENV_VAR} environment variable to a GitHub access token, or the token that is set is invalid or expired."),
         }
     }
+}
+
+/// Returns why the GitHub API refused the request if the error is a refusal
+fn refusal_of(e: &axoupdater::AxoupdateError) -> Option<Refusal> {
+    let axoupdater::AxoupdateError::Reqwest(e) = e else {
+        return None;
+    };
+    match e.status()? {
+        reqwest::StatusCode::TOO_MANY_REQUESTS => Some(Refusal::RateLimited),
+        reqwest::StatusCode::UNAUTHORIZED => Some(Refusal::InvalidToken),
+        reqwest::StatusCode::FORBIDDEN => Some(query_forbidden_reason()),
+        _ => None,
+    }
+}
+
+/// The headers of the refused request aren't available from axoupdater, so the rate limit status is requested to find out why it was forbidden
+fn query_forbidden_reason() -> Refusal {
+    let mut request = reqwest::blocking::Client::new()
+        .get(GITHUB_RATE_LIMIT_URL)
+        .header(reqwest::header::USER_AGENT, APP_NAME);
+    if let Ok(t) = env::var(GITHUB_TOKEN_ENV_VAR) {
+        request = request.bearer_auth(t);
+    }
+    match request.send() {
+        Ok(response) => forbidden_reason(response.status(), response.headers()),
+        Err(e) => {
+            log::warn!("Failed requesting the GitHub rate limit: {e}");
+            Refusal::Unknown
+        }
+    }
+}
+
+/// Determines why a request was forbidden from the response to a request for the rate limit status
+fn forbidden_reason(status: reqwest::StatusCode, headers: &reqwest::header::HeaderMap) -> Refusal {
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Refusal::InvalidToken;
+    }
+    let remaining = headers
+        .get("x-ratelimit-remaining")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if remaining == Some(0) {
+        Refusal::RateLimited
+    } else {
+        Refusal::Unknown
+    }
+}
+
+/// Returns true if the error is from connecting to or through the proxy, assuming that requests are sent through one
+fn is_proxy_error(e: &axoupdater::AxoupdateError) -> bool {
+    matches!(e, axoupdater::AxoupdateError::Reqwest(e) if e.is_connect() || e.status().is_some_and(|status| status == reqwest::StatusCode::PROXY_AUTHENTICATION_REQUIRED))
+}
+
+/// Returns the proxy that update requests are sent through, if any.
+///
+/// axoupdater builds its own reqwest clients, which only take their proxy from the environment,
+/// so the proxy of the [`UpdateConfig`] is set in the environment unless a proxy is already set there.
+fn apply_update_proxy() -> io::Result<Option<String>> {
+    let env_proxy = PROXY_ENV_VARS.iter().find_map(|var| env::var(var).ok());
+    let from_config = env_proxy.as_ref().is_none_or(String::is_empty);
+    let Some(proxy) = resolve_proxy(env_proxy, UpdateConfig::load) else {
+        return Ok(None);
+    };
+    if from_config {
+        reqwest::Proxy::all(&proxy).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("The proxy '{proxy}' in the update config is invalid: {e}"),
+            )
+        })?;
+        log::info!("Sending update requests through the proxy '{proxy}'");
+        env::set_var("HTTPS_PROXY", &proxy);
+        env::set_var("HTTP_PROXY", &proxy);
+    }
+    Ok(Some(proxy))
+}
+
+/// Returns the proxy from the environment if it's set and not empty,
+/// otherwise the one from the config, which is only loaded if it's needed
+fn resolve_proxy(
+    env_value: Option<String>,
+    load_config: impl FnOnce() -> UpdateConfig,
+) -> Option<String> {
+    env_value
+        .filter(|value| !value.is_empty())
+        .or_else(|| load_config().proxy.filter(|proxy| !proxy.is_empty()))
+}
+
+/// Check for the environment variable or command line argument to bypass updates
+fn bypass_updates() -> bool {
+    if env_flag_is_set(BYPASS_UPDATES_ENV_VAR) {
+        log::info!("Update bypassed due to environment variable.");
+        return true;
+    }
+    if has_no_update_arg(env::args()) {
+        log::info!("Update bypassed due to {NO_UPDATE_ARG} argument.");
+        return true;
+    }
     false
 }
 
-/// Creates a file in the same directory as the executable which is used to indicate that
-/// updates are disabled (not the best solution...)
-fn create_disable_update_file() -> io::Result<()> {
-    let exe_dir = get_app_install_dir();
-    let disable_updates_file = exe_dir.join(DISABLE_UPDATES_FILE);
-    File::create(disable_updates_file)?;
+/// Returns true if the [`NO_UPDATE_ARG`] is among the arguments, the first argument (the program path) is skipped
+fn has_no_update_arg(args: impl Iterator<Item = String>) -> bool {
+    args.skip(1).any(|arg| arg == NO_UPDATE_ARG)
+}
+
+/// Check for the environment variable to install updates without elevating permissions
+#[cfg(target_os = "windows")]
+fn no_elevation() -> bool {
+    if env_flag_is_set(NO_ELEVATION_ENV_VAR) {
+        log::info!("Elevating permissions is disabled due to environment variable.");
+        return true;
+    }
+    false
+}
+
+/// Returns the install directory set by the [`INSTALL_DIR_ENV_VAR`] environment variable or in the [`UpdateConfig`] if any
+fn custom_install_dir() -> Option<PathBuf> {
+    resolve_custom_install_dir(env::var_os(INSTALL_DIR_ENV_VAR), UpdateConfig::load)
+}
+
+/// Returns the install directory from the value of the [`INSTALL_DIR_ENV_VAR`] environment variable if it's set and not empty,
+/// otherwise the one from the config, which is only loaded if it's needed
+fn resolve_custom_install_dir(
+    env_value: Option<OsString>,
+    load_config: impl FnOnce() -> UpdateConfig,
+) -> Option<PathBuf> {
+    env_value
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| load_config().install_dir)
+}
+
+/// Checks that an install directory set by the user exists and is writable, so an update doesn't fail halfway.
+fn validate_install_dir(install_dir: &Path) -> io::Result<()> {
+    if !install_dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "The install directory '{}' set by {INSTALL_DIR_ENV_VAR} or in the update config does not exist",
+                install_dir.display()
+            ),
+        ));
+    }
+    if !is_dir_writable(install_dir) {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "The install directory '{}' set by {INSTALL_DIR_ENV_VAR} or in the update config is not writable",
+                install_dir.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns true if a file can be created in the directory
+fn is_dir_writable(dir: &Path) -> bool {
+    let probe_file = dir.join(".plotinator_write_probe");
+    if File::create(&probe_file).is_ok() {
+        if let Err(e) = fs::remove_file(&probe_file) {
+            log::warn!("Failed removing '{}': {e}", probe_file.display());
+        }
+        true
+    } else {
+        false
+    }
+}
+
+/// Disables updates in the [`UpdateConfig`]
+fn disable_updates() -> io::Result<()> {
+    let mut config = UpdateConfig::load();
+    config.updates_disabled = true;
+    config.save()?;
     log::info!("Updates disabled");
     Ok(())
 }
 
-/// Removes the file that indicates that updates are disabled
-fn remove_disable_update_file() -> io::Result<()> {
-    let exe_dir = get_app_install_dir();
-    let disable_updates_file = exe_dir.join(DISABLE_UPDATES_FILE);
-    fs::remove_file(disable_updates_file)?;
+/// Re-enables updates in the [`UpdateConfig`]
+fn enable_updates() -> io::Result<()> {
+    let mut config = UpdateConfig::load();
+    config.updates_disabled = false;
+    config.save()?;
+    // The file might still be there if migrating it failed
+    let disable_updates_file = get_app_install_dir().join(DISABLE_UPDATES_FILE);
+    if disable_updates_file.exists() {
+        fs::remove_file(disable_updates_file)?;
+    }
     log::info!("Updates re-enabled");
     Ok(())
 }
 
-/// Checks for the file that indicates that updates are disabled
+/// Checks the [`UpdateConfig`] for whether updates are disabled.
+///
+/// Updates used to be disabled by a file next to the executable, if it exists it is migrated to the config.
 fn is_updates_disabled() -> bool {
-    // Get the path of the executable
-    let exe_dir = get_app_install_dir();
-
-    // Check if the plotinator_disable_updates file exists
-    let disable_updates_file = exe_dir.join(DISABLE_UPDATES_FILE);
+    let mut config = UpdateConfig::load();
+    let disable_updates_file = get_app_install_dir().join(DISABLE_UPDATES_FILE);
     if disable_updates_file.exists() {
-        log::warn!("Update bypassed due to presence of '{DISABLE_UPDATES_FILE}' file.");
-        return true;
+        log::info!("Migrating the '{DISABLE_UPDATES_FILE}' file to the update config");
+        config.updates_disabled = true;
+        match config.save() {
+            Ok(()) => {
+                if let Err(e) = fs::remove_file(&disable_updates_file) {
+                    log::warn!("Failed removing '{}': {e}", disable_updates_file.display());
+                }
+            }
+            Err(e) => log::warn!("Failed saving the update config: {e}"),
+        }
     }
-    false
+    if config.updates_disabled {
+        log::warn!("Update bypassed since updates are disabled in the update config.");
+    }
+    config.updates_disabled
 }
 
 /// Uses the [`axoupdater::AxoUpdater`] to query for a newer version than what is currently installed
@@ -318,6 +576,122 @@ mod tests {
         let _check_update = is_update_available().unwrap();
     }
 
+    #[test]
+    fn test_has_no_update_arg() {
+        let args = |args: &[&str]| args.iter().map(|a| (*a).to_owned()).collect::<Vec<_>>();
+        assert!(has_no_update_arg(
+            args(&["plotinator3000", "--no-update"]).into_iter()
+        ));
+        assert!(!has_no_update_arg(args(&["plotinator3000"]).into_iter()));
+        // The program path is not an argument
+        assert!(!has_no_update_arg(args(&["--no-update"]).into_iter()));
+    }
+
+    #[test]
+    fn test_validate_install_dir() -> TestResult {
+        let tmp_dir = tempdir()?;
+        validate_install_dir(tmp_dir.path())?;
+        let missing_dir = tmp_dir.path().join("missing");
+        assert_eq!(
+            validate_install_dir(&missing_dir).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_install_dir() {
+        let config = || UpdateConfig {
+            install_dir: Some(PathBuf::from("/opt/from_config")),
+            ..Default::default()
+        };
+        // The environment variable overrides the config
+        assert_eq!(
+            resolve_custom_install_dir(Some("/opt/from_env".into()), config),
+            Some(PathBuf::from("/opt/from_env"))
+        );
+        assert_eq!(
+            resolve_custom_install_dir(None, config),
+            Some(PathBuf::from("/opt/from_config"))
+        );
+        assert_eq!(
+            resolve_custom_install_dir(Some(OsString::new()), config),
+            Some(PathBuf::from("/opt/from_config"))
+        );
+        assert_eq!(
+            resolve_custom_install_dir(None, UpdateConfig::default),
+            None
+        );
+    }
+
+    #[test]
+    fn test_forbidden_reason() {
+        use reqwest::{header::HeaderMap, StatusCode};
+        let remaining = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-ratelimit-remaining", value.parse().unwrap());
+            headers
+        };
+        assert_eq!(
+            forbidden_reason(StatusCode::OK, &remaining("0")),
+            Refusal::RateLimited
+        );
+        assert_eq!(
+            forbidden_reason(StatusCode::OK, &remaining("42")),
+            Refusal::Unknown
+        );
+        assert_eq!(
+            forbidden_reason(StatusCode::UNAUTHORIZED, &HeaderMap::new()),
+            Refusal::InvalidToken
+        );
+        assert_eq!(
+            forbidden_reason(StatusCode::OK, &HeaderMap::new()),
+            Refusal::Unknown
+        );
+    }
+
+    #[test]
+    fn test_resolve_proxy() {
+        let config = || UpdateConfig {
+            proxy: Some("http://from-config:8080".to_owned()),
+            ..Default::default()
+        };
+        // The environment overrides the config
+        assert_eq!(
+            resolve_proxy(Some("http://from-env:8080".to_owned()), config).as_deref(),
+            Some("http://from-env:8080")
+        );
+        assert_eq!(
+            resolve_proxy(Some(String::new()), config).as_deref(),
+            Some("http://from-config:8080")
+        );
+        assert_eq!(resolve_proxy(None, UpdateConfig::default), None);
+        let empty_config = || UpdateConfig {
+            proxy: Some(String::new()),
+            ..Default::default()
+        };
+        assert_eq!(resolve_proxy(None, empty_config), None);
+    }
+
+    #[test]
+    fn test_plotinator_updater_custom_install_dir() -> TestResult {
+        let tmp_dir = tempdir()?;
+        let install_dir =
+            resolve_custom_install_dir(Some(tmp_dir.path().into()), UpdateConfig::default)
+                .expect("The install dir is set");
+        validate_install_dir(&install_dir)?;
+        let mut updater = PlotinatorUpdater::new()?;
+        updater.set_install_dir(&install_dir);
+        updater.always_update(true);
+        let update_result = updater.run()?.unwrap();
+        assert_eq!(
+            update_result.install_prefix.as_str(),
+            install_dir.to_string_lossy()
+        );
+        assert!(install_dir.join("bin").exists());
+        Ok(())
+    }
+
     #[test]
     fn test_plotinator_updater() -> TestResult {
         let tmp_dir = tempdir()?;
@@ -0,0 +1,102 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::APP_NAME;
+
+const CONFIG_FILE: &str = "update_config.toml";
+
+/// Update preferences, stored as TOML in the storage directory of the app (see [`eframe::storage_dir`])
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct UpdateConfig {
+    /// Updates are not checked for until they are re-enabled
+    pub updates_disabled: bool,
+    /// The directory to install updates in, for installs where it isn't the parent of the directory of the executable
+    pub install_dir: Option<PathBuf>,
+    /// The proxy to send update requests through, e.g. `http://proxy.example.com:8080`, for when it isn't set in the environment
+    pub proxy: Option<String>,
+}
+
+impl UpdateConfig {
+    /// Returns the path of the config file or [`None`] if there's no storage directory on this OS
+    pub fn path() -> Option<PathBuf> {
+        eframe::storage_dir(APP_NAME).map(|dir| dir.join(CONFIG_FILE))
+    }
+
+    /// Loads the config, the default config is returned if there's no config file or it can't be read
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        match Self::load_from(&path) {
+            Ok(config) => config,
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    log::warn!("Failed loading '{}': {e}", path.display());
+                }
+                Self::default()
+            }
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "There's no directory to store the update config in",
+            )
+        })?;
+        self.save_to(&path)
+    }
+
+    fn load_from(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        toml::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use testresult::TestResult;
+
+    #[test]
+    fn test_save_and_load() -> TestResult {
+        let tmp_dir = tempdir()?;
+        let path = tmp_dir.path().join("config").join(CONFIG_FILE);
+        let config = UpdateConfig {
+            updates_disabled: true,
+            install_dir: Some(PathBuf::from("/opt/plotinator3000")),
+            proxy: Some("http://proxy.example.com:8080".to_owned()),
+        };
+        config.save_to(&path)?;
+        assert_eq!(UpdateConfig::load_from(&path)?, config);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_fields_are_default() -> TestResult {
+        let tmp_dir = tempdir()?;
+        let path = tmp_dir.path().join(CONFIG_FILE);
+        fs::write(&path, "updates_disabled = true\n")?;
+        let config = UpdateConfig::load_from(&path)?;
+        assert!(config.updates_disabled);
+        assert_eq!(config.install_dir, None);
+        assert_eq!(config.proxy, None);
+        Ok(())
+    }
+}
@@ -334,9 +334,7 @@ fn ui_show_update_window_central_panel(
                                 .clicked()
                             {
                                 update_cancelled.store(true, Ordering::SeqCst);
-                                // Create the disable updates file
-                                super::create_disable_update_file()
-                                    .expect("Failed to disable updates");
+                                super::disable_updates().expect("Failed to disable updates");
                             }
                         }
                     }
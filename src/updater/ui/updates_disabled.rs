@@ -5,7 +5,7 @@ use std::sync::{
 
 use egui::RichText;
 
-use crate::{updater::remove_disable_update_file, APP_NAME};
+use crate::{updater::enable_updates, APP_NAME};
 
 /// Display a simple window that allows users to re-enable automatic updates
 /// or click `continue...` to open the app
@@ -57,8 +57,7 @@ pub(crate) fn show_simple_updates_are_disabled_window() -> eframe::Result<bool>
                         .button(RichText::new("Re-enable updates").strong().size(18.0))
                         .clicked()
                     {
-                        // Remove the disable updates file
-                        remove_disable_update_file().expect("Failed to disable updates");
+                        enable_updates().expect("Failed to re-enable updates");
                         re_enable_updates.store(true, Ordering::SeqCst);
                     }
                 }
@@ -1,8 +1,10 @@
-use std::{ops::RangeInclusive, time::Duration};
+use std::time::Duration;
 
-use chrono::{DateTime, Timelike};
+use chrono::{DateTime, SecondsFormat, Timelike};
 use egui_plot::{GridMark, PlotPoint};
 
+use crate::plot::{DisplayTimeZone, NumberFormat};
+
 /// Format a timestamp in milliseconds into `HH:MM:SS.ms`
 pub fn format_ms_timestamp(timestamp_ms: f64) -> String {
     let duration = Duration::from_millis(timestamp_ms as u64);
@@ -19,27 +21,33 @@ pub fn format_ms_timestamp(timestamp_ms: f64) -> String {
     )
 }
 
-/// The first parameter of formatter is the raw tick value as f64. The second parameter of formatter is the currently shown range on this axis.
+/// Formats a tick on the time axis in the given time zone.
 ///
 /// Assumes x is time in nanoseconds
-pub fn format_time(mark: GridMark, _range: &RangeInclusive<f64>) -> String {
+pub fn format_time(mark: GridMark, time_zone: DisplayTimeZone) -> String {
     let ns = mark.value;
     let sec = ns / NANOS_PER_SEC as f64;
     let ns_remainder = sec.fract() * NANOS_PER_SEC as f64;
     let dt = DateTime::from_timestamp(sec as i64, ns_remainder as u32)
         .unwrap_or_else(|| panic!("Timestamp value out of range: {sec}"));
-    dt.format("%Y-%m-%d %H:%M:%S").to_string()
+    time_zone
+        .convert(dt)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
 }
 
 const NANOS_PER_SEC: u64 = 1_000_000_000;
 
+/// Formats the cursor readout of a plot with the time in the given time zone.
+///
 /// Assumes x is time in nanoseconds
-pub fn format_label_ns(plot_name: &str, val: &PlotPoint) -> String {
+pub fn format_label_ns(plot_name: &str, val: &PlotPoint, time_zone: DisplayTimeZone) -> String {
     let time_ns = val.x;
     let time_s = time_ns / NANOS_PER_SEC as f64;
     let remainder_ns = time_s.fract() * NANOS_PER_SEC as f64;
     let dt = DateTime::from_timestamp(time_s as i64, remainder_ns as u32)
         .unwrap_or_else(|| panic!("Timestamp value out of range: {time_ns}"));
+    let dt = time_zone.convert(dt);
     format!(
         "{plot_name}\ny: {y:.4}\n{h:02}:{m:02}:{s:02}.{subsec_ms:03}",
         y = val.y,
@@ -50,6 +58,17 @@ pub fn format_label_ns(plot_name: &str, val: &PlotPoint) -> String {
     )
 }
 
+/// Formats a sample of a plot for pasting into e.g. a bug report, with the time both as an ISO 8601 UTC timestamp and in nanoseconds.
+///
+/// Assumes x is time in nanoseconds
+pub fn format_sample_for_clipboard(plot_name: &str, time_ns: f64, y: f64) -> String {
+    let utc = DateTime::from_timestamp_nanos(time_ns as i64);
+    format!(
+        "{plot_name}: {y} at {iso} ({time_ns} ns)",
+        iso = utc.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+    )
+}
+
 pub fn format_delta_xy(delta_x_time_s: f64, delta_y: f64) -> String {
     format!(
         "Δt:{delta_x}\nΔy:{delta_y:.4}",
@@ -57,6 +76,16 @@ pub fn format_delta_xy(delta_x_time_s: f64, delta_y: f64) -> String {
     )
 }
 
+/// Formats a frequency in Hz with a suitable SI prefix
+pub fn format_frequency(hz: f64) -> String {
+    match hz {
+        f if f < 1.0 => format!("{:.3}mHz", f * 1000.0),
+        f if f < 1000.0 => format!("{f:.3}Hz"),
+        f if f < 1_000_000.0 => format!("{:.3}kHz", f / 1000.0),
+        f => format!("{:.3}MHz", f / 1_000_000.0),
+    }
+}
+
 /// Formats seconds to a human readable strings from milliseconds up to days.
 pub fn format_time_s(time_s: f64) -> String {
     const SECOND: f64 = 1.0;
@@ -100,6 +129,33 @@ fn div_rem(dividend: f64, divisor: f64) -> (u32, f64) {
     (quotient, remainder)
 }
 
+/// Format a large number in the given format, e.g. `1234567` becomes `1,234,567` with the default format or `1.23 M` with SI prefixes
+#[must_use]
+pub fn format_large_number(number: u64, number_format: NumberFormat) -> String {
+    const SI_PREFIXES: [&str; 6] = ["k", "M", "G", "T", "P", "E"];
+    if number_format.si_prefix && number >= 1000 {
+        let mut value = number as f64 / 1000.0;
+        let mut prefix_idx = 0;
+        // Also step up when the value would be rounded up to 1000.00
+        while value >= 999.995 && prefix_idx < SI_PREFIXES.len() - 1 {
+            value /= 1000.0;
+            prefix_idx += 1;
+        }
+        let value =
+            format!("{value:.2}").replace('.', &number_format.decimal_separator.to_string());
+        return format!("{value} {}", SI_PREFIXES[prefix_idx]);
+    }
+    let digits = number.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (idx, digit) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx) % 3 == 0 {
+            formatted.push(number_format.thousands_separator);
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
 /// Format a value to a human readable byte magnitude description
 #[must_use]
 pub fn format_data_size(size_bytes: usize) -> String {
@@ -127,3 +183,70 @@ pub fn format_data_size(size_bytes: usize) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_sample_for_clipboard() {
+        assert_eq!(
+            format_sample_for_clipboard("RPM #1", 1_700_000_000_500_000_000.0, 2500.5),
+            "RPM #1: 2500.5 at 2023-11-14T22:13:20.500Z (1700000000500000000 ns)"
+        );
+    }
+
+    #[test]
+    fn test_format_large_number() {
+        let format = NumberFormat::default();
+        assert_eq!(format_large_number(0, format), "0");
+        assert_eq!(format_large_number(999, format), "999");
+        assert_eq!(format_large_number(1000, format), "1,000");
+        assert_eq!(format_large_number(1_234_567, format), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_large_number_with_space_separator() {
+        let format = NumberFormat {
+            thousands_separator: ' ',
+            ..Default::default()
+        };
+        assert_eq!(format_large_number(999, format), "999");
+        assert_eq!(format_large_number(1_234_567, format), "1 234 567");
+    }
+
+    #[test]
+    fn test_format_large_number_with_period_separator() {
+        let format = NumberFormat {
+            thousands_separator: '.',
+            decimal_separator: ',',
+            ..Default::default()
+        };
+        assert_eq!(format_large_number(1000, format), "1.000");
+        assert_eq!(format_large_number(1_234_567, format), "1.234.567");
+    }
+
+    #[test]
+    fn test_format_large_number_with_si_prefix() {
+        let format = NumberFormat {
+            si_prefix: true,
+            ..Default::default()
+        };
+        assert_eq!(format_large_number(999, format), "999");
+        assert_eq!(format_large_number(1000, format), "1.00 k");
+        assert_eq!(format_large_number(1_234_567, format), "1.23 M");
+        assert_eq!(format_large_number(999_999, format), "1.00 M");
+        assert_eq!(format_large_number(u64::MAX, format), "18.45 E");
+    }
+
+    #[test]
+    fn test_format_large_number_with_si_prefix_and_decimal_comma() {
+        let format = NumberFormat {
+            thousands_separator: '.',
+            decimal_separator: ',',
+            si_prefix: true,
+        };
+        assert_eq!(format_large_number(1_234_567, format), "1,23 M");
+        assert_eq!(format_large_number(2_500_000_000, format), "2,50 G");
+    }
+}